@@ -1,13 +1,17 @@
 use crate::containers::parameter_types::*;
 use crate::containers::response_types::*;
-use crate::error::Result;
-use crate::podman_service::PodmanService;
+use crate::error::{PodtenderError, Result};
+use crate::podman_service::network_internals::{self, StreamType};
+use crate::podman_service::{ApiVersion, PodmanService};
 use crate::podtender_errors::PodmanErrorResponse;
 use crate::utils;
-use futures::Stream;
+use futures::{AsyncReadExt, AsyncWriteExt, Sink, SinkExt, Stream, TryStreamExt};
+use hyper::body::Bytes;
 use hyper::http;
+use std::collections::HashMap;
 use std::convert::TryInto;
-#[cfg(feature = "enable-tracing")]
+use tokio_util::compat::TokioAsyncReadCompatExt;
+#[cfg(feature = "tracing")]
 use tracing::instrument;
 
 /// Container operations.
@@ -22,48 +26,44 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerCreateLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.create"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.create"))]
     pub async fn create(
         &self,
         parameter: CreateContainerParameter,
     ) -> Result<CreateContainerResponse> {
-        let endpoint = utils::create_endpoint("/libpod/containers/create");
+        let endpoint = "/libpod/containers/create";
         let body = serde_json::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, None, None, Some(body))
+            .post_request(endpoint, None, None, Some(body))
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerListLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.list"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.list"))]
     pub async fn list(
         &self,
         parameter: ListContainersParameter,
     ) -> Result<Vec<ListContainersResponseEntry>> {
         let parameter: ListContainersParameterQuery = parameter.try_into()?;
-        let endpoint = utils::create_endpoint("/libpod/containers/json");
+        let endpoint = "/libpod/containers/json";
 
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .get_request(&endpoint, Some(query), None, None)
+            .get_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerDeleteLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.delete"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.delete"))]
     pub async fn delete(
         self,
         parameter: DeleteContainerParameter,
     ) -> Result<Option<Vec<ContainerDeleteResponseEntry>>> {
-        let endpoint = format!(
-            "{}{}",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -88,13 +88,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerStartLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.start"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.start"))]
     pub async fn start(&self, parameter: StartContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/start",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/start", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -104,13 +100,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerStopLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.stop"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.stop"))]
     pub async fn stop(&self, parameter: StopContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/stop",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/stop", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -119,18 +111,57 @@ impl<'service> Containers<'service> {
         utils::check_service_response_for_error(service_response)
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerAttachLibpod>
+    /// Returns a sink forwarding bytes to the stdin of the container and a stream of its
+    /// demultiplexed output. Closing the sink closes stdin. Sending the `detach_keys` sequence
+    /// through the sink detaches from the container and ends the output stream.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.attach"))]
+    pub async fn attach(
+        &self,
+        parameter: AttachContainerParameter,
+    ) -> Result<(
+        impl Sink<Vec<u8>, Error = PodtenderError>,
+        impl Stream<Item = Result<AttachOutput>>,
+    )> {
+        // Output of containers with a tty isn't multiplexed.
+        let inspect_parameter = InspectContainerParameter {
+            container_name: parameter.container_name.clone(),
+            size: None,
+        };
+        let tty = self
+            .inspect(inspect_parameter)
+            .await?
+            .config
+            .and_then(|config| config.tty)
+            .unwrap_or(false);
+
+        let endpoint = format!("/libpod/containers/{}/attach", parameter.container_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let connection = self
+            .podman_service
+            .post_upgrade_request(&endpoint, Some(query), None, None)
+            .await?;
+
+        let (reader, writer) = connection.compat().split();
+        let stdin = writer.into_sink().sink_map_err(Into::into);
+        let output = network_internals::demultiplex(reader, tty).map_ok(|frame| {
+            match frame.stream_type {
+                // Like the docker cli, treat stdin frames as stdout.
+                StreamType::Stdin | StreamType::Stdout => AttachOutput::Stdout(frame.payload),
+                StreamType::Stderr => AttachOutput::Stderr(frame.payload),
+            }
+        });
+        Ok((stdin, output))
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerCheckpointLibpod>
     /// Needs CRIU and root.
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.checkpoint"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.checkpoint"))]
     pub async fn checkpoint(
         &self,
         parameter: CheckpointContainerParameter,
     ) -> Result<impl Stream<Item = Result<Vec<u8>>>> {
-        let endpoint = format!(
-            "{}{}/checkpoint",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/checkpoint", parameter.container_name);
 
         let query = serde_qs::to_string(&parameter)?;
 
@@ -142,13 +173,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerExistsLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.exists"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.exists"))]
     pub async fn exists(&self, parameter: ContainerExistsParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/exists",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/exists", parameter.container_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -156,17 +183,108 @@ impl<'service> Containers<'service> {
         utils::check_service_response_for_error(service_response)
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerChangesLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.changes"))]
+    pub async fn changes(
+        &self,
+        parameter: ContainerChangesParameter,
+    ) -> Result<Vec<FilesystemChange>> {
+        let endpoint = format!("/libpod/containers/{}/changes", parameter.container_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageCommitLibpod>
+    /// Creates a new image from the container's changes.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.commit"))]
+    pub async fn commit(
+        &self,
+        parameter: CommitContainerParameter,
+    ) -> Result<CommitContainerResponse> {
+        let endpoint = "/libpod/commit";
+
+        // Start workaround cause podman only supports query arrays in this format:
+        // changes=change1&changes=change2
+        let query = {
+            use serde::Serialize;
+
+            #[derive(Serialize, Debug)]
+            struct TempChanges {
+                changes: String,
+            }
+            let mut query = serde_qs::to_string(&parameter)?;
+            for changes in parameter.changes.unwrap_or_default() {
+                query.push('&');
+                query.push_str(&serde_qs::to_string(&TempChanges { changes })?);
+            }
+            query
+        };
+        // end workaround
+
+        let service_response = self
+            .podman_service
+            .post_request(endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerArchiveLibpod>
+    /// Returns the metadata of `path` and a tar archive containing it.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.copy_from"))]
+    pub async fn copy_from(
+        &self,
+        parameter: CopyFromContainerParameter,
+    ) -> Result<(
+        Option<ContainerPathStat>,
+        impl Stream<Item = Result<Vec<u8>>>,
+    )> {
+        let endpoint = format!("/libpod/containers/{}/archive", parameter.container_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let (_status_code, result_stream, header_map) = self
+            .podman_service
+            .get_receive_file_chunks_stream(&endpoint, Some(query), None, None)
+            .await?;
+        let path_stat =
+            utils::decode_base64_json_header(&header_map, "X-Docker-Container-Path-Stat")?;
+        Ok((path_stat, result_stream))
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PutContainerArchiveLibpod>
+    /// Extracts the tar archive `file_stream` into `path` inside the container.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(name = "Containers.copy_to", skip(file_stream))
+    )]
+    pub async fn copy_to<S, O, E>(
+        &self,
+        parameter: CopyToContainerParameter,
+        file_stream: S,
+    ) -> Result<()>
+    where
+        S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<PodtenderError> + Send + 'static,
+    {
+        let endpoint = format!("/libpod/containers/{}/archive", parameter.container_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .put_send_file_chunks_stream(&endpoint, Some(query), None, Some(file_stream))
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerExportLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.export"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.export"))]
     pub async fn export(
         &self,
         parameter: ExportContainerParameter,
     ) -> Result<impl Stream<Item = Result<Vec<u8>>>> {
-        let endpoint = format!(
-            "{}{}/export",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/export", parameter.container_name);
 
         let (_status_code, result_stream, _header_map) = self
             .podman_service
@@ -175,20 +293,60 @@ impl<'service> Containers<'service> {
         Ok(result_stream)
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/GenerateSystemdLibpod>
+    /// Returns the unit files by service name without the `.service` suffix, e.g.
+    /// `container-name`. See `Pods::generate_systemd` for the units of a pod.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.generate_systemd"))]
+    pub async fn generate_systemd(
+        &self,
+        parameter: GenerateSystemdParameter,
+    ) -> Result<HashMap<String, String>> {
+        let endpoint = format!("/libpod/generate/{}/systemd", parameter.name);
+
+        // Start workaround cause podman only supports query arrays in this format:
+        // wants=unit1&wants=unit2
+        let query = {
+            let mut query = serde_qs::to_string(&parameter)?;
+            let arrays = [
+                ("wants", &parameter.wants),
+                ("after", &parameter.after),
+                ("requires", &parameter.requires),
+                (
+                    "additionalEnvVariables",
+                    &parameter.additional_env_variables,
+                ),
+            ];
+            for (key, values) in arrays {
+                for value in values.iter().flatten() {
+                    let mut temp = HashMap::new();
+                    temp.insert(key, value);
+                    if !query.is_empty() {
+                        query.push('&');
+                    }
+                    query.push_str(&serde_qs::to_string(&temp)?);
+                }
+            }
+            query
+        };
+        // end workaround
+
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerHealthcheckLibpod>
     /// Only supported by containers created with docker's format, oci spec doesn't support
     /// healthcheck, see <https://github.com/opencontainers/image-spec/issues/749>
-    #[cfg_attr(
-        feature = "enable-tracing",
-        instrument(name = "Containers.healthcheck")
-    )]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.healthcheck"))]
     pub async fn healthcheck(
         &self,
         parameter: HealthcheckContainerParameter,
     ) -> Result<HealthcheckContainerResponse> {
         let endpoint = format!(
-            "{}{}/healthcheck",
-            utils::create_endpoint("/libpod/containers/"),
+            "/libpod/containers/{}/healthcheck",
             parameter.container_name
         );
         let service_response = self
@@ -199,13 +357,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerInitLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.initialize"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.initialize"))]
     pub async fn initialize(&self, parameter: InitializeContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/init",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/init", parameter.container_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -217,16 +371,12 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerInspectLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.inspect"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.inspect"))]
     pub async fn inspect(
         &self,
         parameter: InspectContainerParameter,
     ) -> Result<InspectContainerResponse> {
-        let endpoint = format!(
-            "{}{}/json",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/json", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -237,13 +387,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerKillLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.kill"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.kill"))]
     pub async fn kill(&self, parameter: KillContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/kill",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/kill", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -253,32 +399,30 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerLogsLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.logs"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.logs"))]
     pub async fn logs(
         &self,
         parameter: ContainerLogsParameter,
-    ) -> Result<impl Stream<Item = Result<String>>> {
-        let endpoint = format!(
-            "{}{}/logs",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+    ) -> Result<impl Stream<Item = Result<LogFrame>>> {
+        let endpoint = format!("/libpod/containers/{}/logs", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let (_status_code, result_stream, _header_map) = self
             .podman_service
-            .get_lines_stream(&endpoint, Some(query), None, None)
+            .get_log_frames_stream(
+                &endpoint,
+                Some(query),
+                None,
+                None,
+                parameter.timestamps.unwrap_or(false),
+            )
             .await?;
         Ok(result_stream)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerMountLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.mount"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.mount"))]
     pub async fn mount(&self, parameter: MountContainerParameter) -> Result<String> {
-        let endpoint = format!(
-            "{}{}/mount",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/mount", parameter.container_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -290,13 +434,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerPauseLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.pause"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.pause"))]
     pub async fn pause(&self, parameter: PauseContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/pause",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/pause", parameter.container_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -305,13 +445,22 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerRenameLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.rename"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.rename"))]
     pub async fn rename(&self, parameter: RenameContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/rename",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/rename", parameter.container_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerResizeLibpod>
+    /// Only containers created with a tty can be resized.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.resize"))]
+    pub async fn resize(&self, parameter: ResizeContainerParameter) -> Result<()> {
+        let endpoint = format!("/libpod/containers/{}/resize", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -320,14 +469,31 @@ impl<'service> Containers<'service> {
         utils::check_service_response_for_error(service_response)
     }
 
+    /// Resizes the container's tty on every size received from `sizes`, e.g. the terminal's
+    /// resize events. Completes once `sizes` ends or a resize fails, run it alongside
+    /// `Containers::attach` and drop it when the session ends.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(name = "Containers.forward_resizes", skip(sizes))
+    )]
+    pub async fn forward_resizes<S>(&self, container_name: String, sizes: S) -> Result<()>
+    where
+        S: Stream<Item = TerminalSize>,
+    {
+        utils::forward_resizes(sizes, |size| {
+            self.resize(ResizeContainerParameter {
+                container_name: container_name.clone(),
+                height: Some(size.height),
+                width: Some(size.width),
+            })
+        })
+        .await
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerRestartLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.restart"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.restart"))]
     pub async fn restart(&self, parameter: RestartContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/restart",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/restart", parameter.container_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -336,20 +502,61 @@ impl<'service> Containers<'service> {
         utils::check_service_response_for_error(service_response)
     }
 
-    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerTopLibpod>
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerRestoreLibpod>
+    /// Restores a container from a checkpoint kept in local storage. Needs CRIU and root.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.restore"))]
+    pub async fn restore(
+        &self,
+        parameter: RestoreContainerParameter,
+    ) -> Result<RestoreContainerResponse> {
+        let endpoint = format!("/libpod/containers/{}/restore", parameter.container_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerRestoreLibpod>
+    /// Restores a container from a checkpoint archive, e.g. exported by `Containers::checkpoint`
+    /// on another host. `container_name` is ignored, set `name` to rename the restored container.
+    /// Needs CRIU and root.
     #[cfg_attr(
-        feature = "enable-tracing",
-        instrument(name = "Containers.list_processes")
+        feature = "tracing",
+        instrument(name = "Containers.restore_with_import", skip(file_stream))
     )]
+    pub async fn restore_with_import<S, O, E>(
+        &self,
+        parameter: RestoreContainerParameter,
+        file_stream: S,
+    ) -> Result<RestoreContainerResponse>
+    where
+        S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<PodtenderError> + Send + 'static,
+    {
+        // Podman ignores the container name when importing a checkpoint.
+        let endpoint = "/libpod/containers/import/restore";
+        let parameter = RestoreContainerParameter {
+            import: Some(true),
+            ..parameter
+        };
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_send_file_chunks_stream(endpoint, Some(query), None, Some(file_stream))
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerTopLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.list_processes"))]
     pub async fn list_processes(
         &self,
         parameter: ListContainerProcessesParameter,
     ) -> Result<ListContainerProcessesResponse> {
-        let endpoint = format!(
-            "{}{}/top",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/top", parameter.container_name);
 
         let parameter = ListContainerProcessesParameter {
             stream: Some(false),
@@ -366,18 +573,14 @@ impl<'service> Containers<'service> {
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerTopLibpod>
     #[cfg_attr(
-        feature = "enable-tracing",
+        feature = "tracing",
         instrument(name = "Containers.list_processes_streaming")
     )]
     pub async fn list_processes_streaming(
         &self,
         parameter: ListContainerProcessesParameter,
     ) -> Result<impl Stream<Item = Result<ListContainerProcessesResponse>>> {
-        let endpoint = format!(
-            "{}{}/top",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/top", parameter.container_name);
         let parameter = ListContainerProcessesParameter {
             stream: Some(true),
             ..parameter
@@ -392,13 +595,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerUnmountLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.unmount"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.unmount"))]
     pub async fn unmount(&self, parameter: UnmountContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/unmount",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/unmount", parameter.container_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -407,13 +606,9 @@ impl<'service> Containers<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerUnpauseLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.unpause"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.unpause"))]
     pub async fn unpause(&self, parameter: UnpauseContainerParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/unpause",
-            utils::create_endpoint("/libpod/containers/"),
-            parameter.container_name
-        );
+        let endpoint = format!("/libpod/containers/{}/unpause", parameter.container_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -421,16 +616,68 @@ impl<'service> Containers<'service> {
         utils::check_service_response_for_error(service_response)
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerUpdateLibpod>
+    /// Updates the resource limits of a container, running containers are updated in place.
+    /// `restart_policy` and `restart_retries` require podman 4.8.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.update"))]
+    pub async fn update(&self, parameter: UpdateContainerParameter) -> Result<()> {
+        if parameter.restart_policy.is_some() || parameter.restart_retries.is_some() {
+            self.podman_service
+                .require_api_version(ApiVersion::new(4, 8, 0))
+                .await?;
+        }
+        let endpoint = format!("/libpod/containers/{}/update", parameter.container_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let body = serde_json::to_string(&parameter.resources)?;
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, Some(body))
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerWaitLibpod>
+    /// Waits until one of the conditions is met and returns the exit code of the container.
+    /// Dropping the returned future cancels the wait, so it can be used with `tokio::select!` or
+    /// timeouts.
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.wait"))]
+    pub async fn wait(&self, parameter: WaitContainerParameter) -> Result<i32> {
+        let endpoint = format!("/libpod/containers/{}/wait", parameter.container_name);
+
+        // Start workaround cause podman only supports query arrays in this format:
+        // condition=running&condition=stopped
+        let query = {
+            use serde::Serialize;
+
+            #[derive(Serialize, Debug)]
+            struct TempCondition {
+                condition: WaitCondition,
+            }
+            let mut query = serde_qs::to_string(&parameter)?;
+            for condition in parameter.condition.unwrap_or_default() {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                query.push_str(&serde_qs::to_string(&TempCondition { condition })?);
+            }
+            query
+        };
+        // end workaround
+
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainersStatsAllLibpod>
-    #[cfg_attr(
-        feature = "enable-tracing",
-        instrument(name = "Containers.stats_streaming")
-    )]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.stats_streaming"))]
     pub async fn stats_stream(
         &self,
         parameter: ContainersStatsParameter,
     ) -> Result<impl Stream<Item = Result<ContainerStatsResponse>>> {
-        let endpoint = format!("{}stats", utils::create_endpoint("/libpod/containers/"));
+        let endpoint = "/libpod/containers/stats";
         let parameter = ContainersStatsParameter {
             stream: Some(true),
             ..parameter
@@ -476,18 +723,18 @@ impl<'service> Containers<'service> {
 
         let (_status_code, result_stream, _header_map) = self
             .podman_service
-            .get_json_stream(&endpoint, Some(query), None, None)
+            .get_json_stream(endpoint, Some(query), None, None)
             .await?;
         Ok(result_stream)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainersStatsAllLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.stats"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.stats"))]
     pub async fn stats(
         &self,
         parameter: ContainersStatsParameter,
     ) -> Result<ContainerStatsResponse> {
-        let endpoint = format!("{}stats", utils::create_endpoint("/libpod/containers/"));
+        let endpoint = "/libpod/containers/stats";
         let parameter = ContainersStatsParameter {
             stream: Some(false),
             ..parameter
@@ -534,24 +781,24 @@ impl<'service> Containers<'service> {
 
         let service_response = self
             .podman_service
-            .get_request(&endpoint, Some(query), None, None)
+            .get_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerPruneLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Containers.prune"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Containers.prune"))]
     pub async fn prune(
         &self,
         parameter: PruneContainersParameter,
     ) -> Result<Vec<PruneContainerResponseEntry>> {
         let parameter: PruneContainersParameterQuery = parameter.try_into()?;
-        let endpoint = utils::create_endpoint("/libpod/containers/prune");
+        let endpoint = "/libpod/containers/prune";
 
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, Some(query), None, None)
+            .post_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
@@ -0,0 +1,83 @@
+//! Helpers to create and extract the tar archives used by `Containers::copy_to` and
+//! `Containers::copy_from`. Requires the `tar` feature.
+//!
+//! The archives are built and extracted in memory with blocking file system io, wrap the calls in
+//! `tokio::task::spawn_blocking` when copying large directories.
+
+use crate::error::Result;
+use futures::{Stream, TryStreamExt};
+use std::path::Path;
+
+/// Creates a tar archive containing the file or directory at `path`. The archive entry is named
+/// after the last component of `path`, so extracting it into `/tmp` results in `/tmp/<name>`.
+pub fn tar_from_host_path(path: impl AsRef<Path>) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    let name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} has no file name", path.display()),
+        )
+    })?;
+
+    let mut builder = tar::Builder::new(Vec::new());
+    if path.is_dir() {
+        builder.append_dir_all(name, path)?;
+    } else {
+        builder.append_path_with_name(path, name)?;
+    }
+    Ok(builder.into_inner()?)
+}
+
+/// Same as [`tar_from_host_path`] but returns a stream which can be passed to
+/// `Containers::copy_to` directly.
+pub fn tar_stream_from_host_path(
+    path: impl AsRef<Path>,
+) -> Result<impl Stream<Item = Result<Vec<u8>>>> {
+    let archive = tar_from_host_path(path)?;
+    Ok(futures::stream::once(futures::future::ok(archive)))
+}
+
+/// Extracts a tar archive into the directory `destination`.
+pub fn unpack_tar_to_host_path(archive: &[u8], destination: impl AsRef<Path>) -> Result<()> {
+    tar::Archive::new(archive).unpack(destination)?;
+    Ok(())
+}
+
+/// Collects a stream of tar chunks, e.g. returned by `Containers::copy_from`, and extracts it
+/// into the directory `destination`.
+pub async fn unpack_tar_stream_to_host_path<S>(
+    archive_stream: S,
+    destination: impl AsRef<Path>,
+) -> Result<()>
+where
+    S: Stream<Item = Result<Vec<u8>>>,
+{
+    let archive: Vec<u8> = archive_stream
+        .try_fold(Vec::new(), |mut archive, mut chunk| async move {
+            archive.append(&mut chunk);
+            Ok(archive)
+        })
+        .await?;
+    unpack_tar_to_host_path(&archive, destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_directory() {
+        let root = std::env::temp_dir().join(format!("podtender_archive_{}", std::process::id()));
+        let source = root.join("source");
+        std::fs::create_dir_all(source.join("nested")).unwrap();
+        std::fs::write(source.join("nested/file.txt"), b"content").unwrap();
+
+        let archive = tar_from_host_path(&source).unwrap();
+        let destination = root.join("destination");
+        unpack_tar_to_host_path(&archive, &destination).unwrap();
+
+        let content = std::fs::read(destination.join("source/nested/file.txt")).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(b"content".to_vec(), content);
+    }
+}
@@ -2,6 +2,9 @@
 
 mod api_call_functions;
 
+#[cfg(feature = "tar")]
+pub mod archive;
+
 /// Parameter types for container operations.
 pub mod parameter_types;
 /// Response types for container operations.
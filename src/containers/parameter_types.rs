@@ -424,7 +424,7 @@ pub struct LinuxRdma {
 #[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
 pub struct LinuxBlockIO {
     #[serde(rename = "leafWeight")]
-    leaf_weight: Option<u16>,
+    pub leaf_weight: Option<u16>,
     #[serde(rename = "throttleReadBpsDevice")]
     pub throttle_read_bps_device: Option<Vec<LinuxThrottleDevice>>,
     #[serde(rename = "throttleReadIOPSDevice")]
@@ -444,10 +444,10 @@ pub struct LinuxBlockIO {
 #[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
 pub struct LinuxWeightDevice {
     #[serde(rename = "leafWeight")]
-    leaf_weight: Option<u16>,
-    major: Option<i64>,
-    minor: Option<i64>,
-    weight: Option<u16>,
+    pub leaf_weight: Option<u16>,
+    pub major: Option<i64>,
+    pub minor: Option<i64>,
+    pub weight: Option<u16>,
 }
 
 #[skip_serializing_none]
@@ -458,11 +458,11 @@ pub struct LinuxCPU {
     pub cpus: Option<String>,
     pub mems: Option<String>,
     pub period: Option<u64>,
-    pub quote: Option<i64>,
+    pub quota: Option<i64>,
     #[serde(rename = "realtimePeriod")]
     pub realtime_period: Option<u64>,
     #[serde(rename = "realtimeRuntime")]
-    pub reatlime_runtime: Option<i64>,
+    pub realtime_runtime: Option<i64>,
     pub shares: Option<u64>,
 }
 
@@ -497,12 +497,13 @@ pub struct LinuxMemory {
     #[serde(rename = "disableOOMKiller")]
     pub disable_oom_killer: Option<bool>,
     pub kernel: Option<i64>,
-    #[serde(rename = "KernelTCP")]
+    #[serde(rename = "kernelTCP")]
     pub kernel_tcp: Option<i64>,
     pub limit: Option<i64>,
     pub reservation: Option<i64>,
     pub swap: Option<i64>,
     pub swappiness: Option<u64>,
+    #[serde(rename = "useHierarchy")]
     pub use_hierarchy: Option<bool>,
 }
 
@@ -511,6 +512,7 @@ pub struct LinuxMemory {
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
 pub struct LinuxNetwork {
+    #[serde(rename = "classID")]
     pub class_id: Option<u32>,
     pub priorities: Option<Vec<LinuxInterfacePriority>>,
 }
@@ -710,6 +712,74 @@ impl ExampleValues for ContainerExistsParameter {
     }
 }
 
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct AttachContainerParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    /// Key sequence that detaches from the container when sent via stdin, e.g. `ctrl-p,ctrl-q`.
+    #[serde(rename = "detachKeys")]
+    pub detach_keys: Option<String>,
+    /// Replay the container logs before attaching.
+    pub logs: Option<bool>,
+    pub stderr: Option<bool>,
+    pub stdin: Option<bool>,
+    pub stdout: Option<bool>,
+    pub stream: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for AttachContainerParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("AttachContainerParameter"),
+            detach_keys: Some(String::from("ctrl-p,ctrl-q")),
+            logs: Some(false),
+            stderr: Some(true),
+            stdin: Some(true),
+            stdout: Some(true),
+            stream: Some(true),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ContainerChangesParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    #[serde(rename = "diffType")]
+    pub diff_type: Option<DiffType>,
+    /// Layer or image to compare against, defaults to the container's image.
+    pub parent: Option<String>,
+}
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ContainerChangesParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("ContainerChangesParameter"),
+            diff_type: Some(DiffType::Container),
+            parent: None,
+        }
+    }
+}
+
+// also used in `ImageChangesParameter`
+/// Which layers to compare when requesting filesystem changes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffType {
+    All,
+    Container,
+    Image,
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
@@ -743,6 +813,95 @@ impl ExampleValues for CheckpointContainerParameter {
     }
 }
 
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct CommitContainerParameter {
+    #[serde(rename = "container")]
+    pub container_name: String,
+    pub author: Option<String>,
+    /// Dockerfile instructions applied to the image, e.g. `CMD ["sh"]`.
+    #[serde(skip_serializing)]
+    pub changes: Option<Vec<String>>,
+    /// Commit message.
+    pub comment: Option<String>,
+    /// Image format, `oci` or `docker`.
+    pub format: Option<String>,
+    /// Pause the container while committing.
+    pub pause: Option<bool>,
+    /// Name of the new image.
+    pub repo: Option<String>,
+    /// Squash the new layers into a single layer.
+    pub squash: Option<bool>,
+    pub tag: Option<String>,
+}
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for CommitContainerParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("CommitContainerParameter"),
+            author: Some(String::from("podtender")),
+            changes: Some(vec![
+                String::from("LABEL podtender=commit"),
+                String::from("ENV PODTENDER=commit"),
+            ]),
+            comment: Some(String::from("CommitContainerParameter")),
+            format: Some(String::from("oci")),
+            pause: Some(true),
+            repo: Some(String::from("localhost/commit_container_parameter")),
+            squash: Some(false),
+            tag: Some(String::from("latest")),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct CopyFromContainerParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    /// Path of the file or directory inside the container.
+    pub path: String,
+}
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for CopyFromContainerParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("CopyFromContainerParameter"),
+            path: String::from("/etc/hostname"),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct CopyToContainerParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    /// Directory inside the container the archive gets extracted to.
+    pub path: String,
+    /// Pause the container while copying.
+    pub pause: Option<bool>,
+}
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for CopyToContainerParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("CopyToContainerParameter"),
+            path: String::from("/tmp"),
+            pause: Some(true),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
@@ -760,6 +919,82 @@ impl ExampleValues for ExportContainerParameter {
     }
 }
 
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct GenerateSystemdParameter {
+    /// Name or id of the container or pod. Units of a pod include the units of its containers.
+    #[serde(skip_serializing)]
+    pub name: String,
+    /// Use the name instead of the id of containers and pods in the units.
+    #[serde(rename = "useName")]
+    pub use_name: Option<bool>,
+    /// Create new containers and pods when the units start instead of starting the existing
+    /// ones.
+    pub new: Option<bool>,
+    /// Leave out the header with podman version and generation time.
+    #[serde(rename = "noHeader")]
+    pub no_header: Option<bool>,
+    /// Start timeout in seconds.
+    #[serde(rename = "startTimeout")]
+    pub start_timeout: Option<u32>,
+    /// Stop timeout in seconds.
+    #[serde(rename = "stopTimeout")]
+    pub stop_timeout: Option<u32>,
+    /// Systemd restart policy: `no`, `on-success`, `on-failure`, `on-abnormal`, `on-watchdog`,
+    /// `on-abort` or `always`.
+    #[serde(rename = "restartPolicy")]
+    pub restart_policy: Option<String>,
+    /// Seconds systemd waits before restarting.
+    #[serde(rename = "restartSec")]
+    pub restart_sec: Option<u32>,
+    /// Prefix of container unit names, podman defaults to `container`.
+    #[serde(rename = "containerPrefix")]
+    pub container_prefix: Option<String>,
+    /// Prefix of pod unit names, podman defaults to `pod`.
+    #[serde(rename = "podPrefix")]
+    pub pod_prefix: Option<String>,
+    /// Separator between prefix and name in unit names, podman defaults to `-`.
+    pub separator: Option<String>,
+    /// Units added as `Wants=`.
+    #[serde(skip_serializing)]
+    pub wants: Option<Vec<String>>,
+    /// Units added as `After=`.
+    #[serde(skip_serializing)]
+    pub after: Option<Vec<String>>,
+    /// Units added as `Requires=`.
+    #[serde(skip_serializing)]
+    pub requires: Option<Vec<String>>,
+    /// Environment variables of the units, e.g. `FOO=bar`.
+    #[serde(skip_serializing)]
+    pub additional_env_variables: Option<Vec<String>>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for GenerateSystemdParameter {
+    fn example() -> Self {
+        Self {
+            name: String::from("GenerateSystemdParameter"),
+            use_name: Some(true),
+            new: Some(false),
+            no_header: Some(true),
+            start_timeout: None,
+            stop_timeout: Some(10),
+            restart_policy: Some(String::from("on-failure")),
+            restart_sec: Some(5),
+            container_prefix: Some(String::from("podtender")),
+            pod_prefix: None,
+            separator: Some(String::from("_")),
+            wants: Some(vec![String::from("network-online.target")]),
+            after: Some(vec![String::from("network-online.target")]),
+            requires: None,
+            additional_env_variables: Some(vec![String::from("PODTENDER=systemd")]),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
@@ -840,12 +1075,16 @@ pub struct ContainerLogsParameter {
     #[serde(skip_serializing)]
     pub container_name: String,
     pub follow: Option<bool>,
-    pub since: Option<bool>,
+    /// Only return logs since this time, as RFC3339 timestamp, unix timestamp or duration
+    /// relative to now, e.g. `10m`.
+    pub since: Option<String>,
     pub stdout: Option<bool>,
     pub stderr: Option<bool>,
-    pub tail: Option<bool>,
+    /// Only return this number of lines from the end of the logs.
+    pub tail: Option<u64>,
     pub timestamps: Option<bool>,
-    pub until: Option<bool>,
+    /// Only return logs before this time, same formats as `since`.
+    pub until: Option<String>,
 }
 #[cfg(any(test, feature = "examples"))]
 impl ExampleValues for ContainerLogsParameter {
@@ -900,6 +1139,38 @@ impl ExampleValues for RenameContainerParameter {
     }
 }
 
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ResizeContainerParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    #[serde(rename = "h")]
+    pub height: Option<u16>,
+    #[serde(rename = "w")]
+    pub width: Option<u16>,
+}
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ResizeContainerParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("ResizeContainerParameter"),
+            height: Some(24),
+            width: Some(80),
+        }
+    }
+}
+
+// also used in `Exec::forward_resizes`
+/// Size of a terminal in rows and columns.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct TerminalSize {
+    pub height: u16,
+    pub width: u16,
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
@@ -931,7 +1202,7 @@ pub struct RestoreContainerParameter {
     pub ignore_root_fs: Option<bool>,
     #[serde(rename = "ignoreStaticIP")]
     pub ignore_static_ip: Option<bool>,
-    #[serde(rename = "igrnoreStaticMAC")]
+    #[serde(rename = "ignoreStaticMAC")]
     pub ignore_static_mac: Option<bool>,
     pub import: Option<bool>,
     pub keep: Option<bool>,
@@ -1025,6 +1296,90 @@ impl ExampleValues for UnpauseContainerParameter {
     }
 }
 
+//query and json
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct UpdateContainerParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    /// New restart policy, e.g. `on-failure`.
+    #[serde(rename = "restartPolicy")]
+    pub restart_policy: Option<String>,
+    /// Number of restart retries, only used with the `on-failure` restart policy.
+    #[serde(rename = "restartRetries")]
+    pub restart_retries: Option<u64>,
+    /// Resource limits to apply, unset fields keep their current value. Sent as json body.
+    #[serde(skip_serializing)]
+    pub resources: LinuxResources,
+}
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for UpdateContainerParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("UpdateContainerParameter"),
+            restart_policy: None,
+            restart_retries: None,
+            resources: LinuxResources {
+                cpu: Some(LinuxCPU {
+                    period: Some(100000),
+                    quota: Some(50000),
+                    ..Default::default()
+                }),
+                memory: Some(LinuxMemory {
+                    limit: Some(268435456),
+                    ..Default::default()
+                }),
+                pids: Some(LinuxPids { limit: 100 }),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct WaitContainerParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    /// Conditions to wait for, podman waits for `stopped` if none are set.
+    #[serde(skip_serializing)]
+    pub condition: Option<Vec<WaitCondition>>,
+    /// Time between checks of the conditions, e.g. `250ms`.
+    pub interval: Option<String>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for WaitContainerParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("WaitContainerParameter"),
+            condition: Some(vec![WaitCondition::Stopped, WaitCondition::Exited]),
+            interval: Some(String::from("250ms")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WaitCondition {
+    Configured,
+    Created,
+    Exited,
+    Healthy,
+    Initialized,
+    Paused,
+    Removing,
+    Running,
+    Stopped,
+    Stopping,
+    Unhealthy,
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
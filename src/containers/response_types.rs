@@ -666,6 +666,123 @@ pub struct ContainerStatsResponseInner {
     pub up_time: Option<u64>,
 }
 
+// also used in `Images::changes`
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FilesystemChange {
+    #[serde(rename = "Path")]
+    pub path: String,
+    #[serde(rename = "Kind")]
+    pub kind: FilesystemChangeKind,
+}
+
+/// Podman sends the kind of change as integer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum FilesystemChangeKind {
+    Modified,
+    Added,
+    Deleted,
+}
+
+impl TryFrom<u8> for FilesystemChangeKind {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FilesystemChangeKind::Modified),
+            1 => Ok(FilesystemChangeKind::Added),
+            2 => Ok(FilesystemChangeKind::Deleted),
+            other => Err(format!("unknown filesystem change kind {}", other)),
+        }
+    }
+}
+
+impl From<FilesystemChangeKind> for u8 {
+    fn from(kind: FilesystemChangeKind) -> Self {
+        match kind {
+            FilesystemChangeKind::Modified => 0,
+            FilesystemChangeKind::Added => 1,
+            FilesystemChangeKind::Deleted => 2,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CommitContainerResponse {
+    /// Id of the new image.
+    #[serde(rename = "Id")]
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RestoreContainerResponse {
+    #[serde(rename = "CRIUStatistics")]
+    pub criu_statistics: Option<CriuCheckpointRestoreStatistics>,
+    #[serde(rename = "Err")]
+    pub err: Option<String>,
+    #[serde(rename = "Id")]
+    pub id: Option<String>,
+    #[serde(rename = "RawInput")]
+    pub raw_input: Option<String>,
+    #[serde(rename = "RuntimeDuration")]
+    pub runtime_duration: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CriuCheckpointRestoreStatistics {
+    pub forking_time: Option<u32>,
+    pub freezing_time: Option<u32>,
+    pub frozen_time: Option<u32>,
+    pub memdump_time: Option<u32>,
+    pub memwrite_time: Option<u32>,
+    pub pages_restored: Option<u64>,
+    pub pages_scanned: Option<u64>,
+    pub pages_written: Option<u64>,
+    pub restore_time: Option<u32>,
+}
+
+/// Output of a container received via `Containers::attach`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AttachOutput {
+    Stdout(Vec<u8>),
+    Stderr(Vec<u8>),
+}
+
+/// A single line of container logs received via `Containers::logs`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LogFrame {
+    pub stream: LogStream,
+    /// Only set if the logs were requested with `timestamps`.
+    pub timestamp: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// Metadata of a file or directory inside a container, sent by podman as
+/// `X-Docker-Container-Path-Stat` header when copying via `Containers::copy_from`.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerPathStat {
+    pub name: Option<String>,
+    pub size: Option<i64>,
+    /// Go's `os.FileMode`, the permission bits are in the lower 9 bits.
+    pub mode: Option<u32>,
+    pub mtime: Option<String>,
+    #[serde(rename = "isDir")]
+    pub is_dir: Option<bool>,
+    #[serde(rename = "linkTarget")]
+    pub link_target: Option<String>,
+}
+
 pub type PruneContainerResponseEntry = ErrIdSizeResponse;
 
 pub type ContainerDeleteResponseEntry = ErrIdResponse;
@@ -1,3 +1,4 @@
+use crate::podman_service::ApiVersion;
 use crate::podtender_errors::PodmanErrorResponse;
 use crate::podtender_errors::RequestError;
 use std::string::FromUtf8Error;
@@ -25,6 +26,23 @@ pub enum PodtenderError {
     FromUtf8Error(#[from] FromUtf8Error),
     #[error(transparent)]
     IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    Base64DecodeError(#[from] base64::DecodeError),
+    #[error("invalid podman service uri `{0}`, expected `unix:///path/to/podman.sock`, `tcp://host:port` or `ssh://[user@]host[:port]/path/to/podman.sock`")]
+    InvalidServiceUri(String),
+    #[error("no podman service found, tried: {}", .0.join(", "))]
+    ServiceNotFound(Vec<String>),
+    #[error("podman system service failed to start, {reason}, stderr:\n{stderr}")]
+    LocalServiceStartFailed { reason: String, stderr: String },
+    #[error("podman listed an image without id")]
+    ImageWithoutId,
+    #[error("invalid podman api version {0}")]
+    InvalidApiVersion(String),
+    #[error("requires podman api version {required}, the service supports {actual}")]
+    UnsupportedByServer {
+        required: ApiVersion,
+        actual: ApiVersion,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, PodtenderError>;
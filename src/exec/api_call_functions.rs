@@ -0,0 +1,129 @@
+use crate::containers::parameter_types::TerminalSize;
+use crate::error::Result;
+use crate::exec::parameter_types::*;
+use crate::exec::response_types::*;
+use crate::podman_service::network_internals::StreamType;
+use crate::podman_service::PodmanService;
+use crate::utils;
+use futures::future::Either;
+use futures::{Stream, StreamExt, TryStreamExt};
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+
+/// Exec operations.
+#[derive(Debug)]
+pub struct Exec<'service> {
+    podman_service: &'service PodmanService,
+}
+
+impl<'service> Exec<'service> {
+    pub(crate) fn new(podman_service: &'service PodmanService) -> Self {
+        Exec { podman_service }
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ContainerExecLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Exec.create"))]
+    pub async fn create(&self, parameter: CreateExecParameter) -> Result<CreateExecResponse> {
+        let endpoint = format!("/libpod/containers/{}/exec", parameter.container_name);
+        let body = serde_json::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, None, None, Some(body))
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ExecStartLibpod>
+    /// Returns the demultiplexed stdout and stderr output of the exec instance. Unless the exec
+    /// instance is detached, the last item of the stream is its exit code.
+    /// Stdin is not forwarded.
+    #[cfg_attr(feature = "tracing", instrument(name = "Exec.start"))]
+    pub async fn start(
+        &self,
+        parameter: StartExecParameter,
+    ) -> Result<impl Stream<Item = Result<ExecOutput>>> {
+        let endpoint = format!("/libpod/exec/{}/start", parameter.exec_id);
+        let body = serde_json::to_string(&parameter)?;
+
+        let (_status_code, output_stream, _header_map) = self
+            .podman_service
+            .post_multiplexed_stream(
+                &endpoint,
+                None,
+                None,
+                Some(body),
+                parameter.tty.unwrap_or(false),
+            )
+            .await?;
+        let output_stream = output_stream.map_ok(|frame| match frame.stream_type {
+            // Like the docker cli, treat stdin frames as stdout.
+            StreamType::Stdin | StreamType::Stdout => ExecOutput::Stdout(frame.payload),
+            StreamType::Stderr => ExecOutput::Stderr(frame.payload),
+        });
+
+        // The exit code is only known after podman closed the output stream.
+        let exit_code = if parameter.detach.unwrap_or(false) {
+            Either::Left(futures::stream::empty())
+        } else {
+            let podman_service = self.podman_service.clone();
+            let inspect_parameter = InspectExecParameter {
+                exec_id: parameter.exec_id,
+            };
+            Either::Right(futures::stream::once(async move {
+                let response = podman_service.exec().inspect(inspect_parameter).await?;
+                if response.running == Some(true) {
+                    Ok(ExecOutput::ExitCode(None))
+                } else {
+                    Ok(ExecOutput::ExitCode(response.exit_code))
+                }
+            }))
+        };
+
+        Ok(output_stream.chain(exit_code))
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ExecInspectLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Exec.inspect"))]
+    pub async fn inspect(&self, parameter: InspectExecParameter) -> Result<InspectExecResponse> {
+        let endpoint = format!("/libpod/exec/{}/json", parameter.exec_id);
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, None, None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ExecResizeLibpod>
+    /// Only exec instances created with a tty can be resized.
+    #[cfg_attr(feature = "tracing", instrument(name = "Exec.resize"))]
+    pub async fn resize(&self, parameter: ResizeExecParameter) -> Result<()> {
+        let endpoint = format!("/libpod/exec/{}/resize", parameter.exec_id);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
+    /// Resizes the exec instance's tty on every size received from `sizes`, e.g. the terminal's
+    /// resize events. Completes once `sizes` ends or a resize fails, run it alongside
+    /// `Exec::start` and drop it when the session ends.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(name = "Exec.forward_resizes", skip(sizes))
+    )]
+    pub async fn forward_resizes<S>(&self, exec_id: String, sizes: S) -> Result<()>
+    where
+        S: Stream<Item = TerminalSize>,
+    {
+        utils::forward_resizes(sizes, |size| {
+            self.resize(ResizeExecParameter {
+                exec_id: exec_id.clone(),
+                height: Some(size.height),
+                width: Some(size.width),
+            })
+        })
+        .await
+    }
+}
@@ -0,0 +1,9 @@
+//! <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#tag/exec>
+
+mod api_call_functions;
+/// Parameter types for exec operations.
+pub mod parameter_types;
+/// Response types for exec operations.
+pub mod response_types;
+
+pub use api_call_functions::*;
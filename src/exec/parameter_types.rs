@@ -0,0 +1,136 @@
+#[cfg(any(test, feature = "examples"))]
+use crate::example_values_trait::ExampleValues;
+#[cfg(feature = "builder")]
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+//json
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct CreateExecParameter {
+    #[serde(skip_serializing)]
+    pub container_name: String,
+    #[serde(rename = "AttachStderr")]
+    pub attach_stderr: Option<bool>,
+    #[serde(rename = "AttachStdin")]
+    pub attach_stdin: Option<bool>,
+    #[serde(rename = "AttachStdout")]
+    pub attach_stdout: Option<bool>,
+    #[serde(rename = "Cmd")]
+    pub cmd: Option<Vec<String>>,
+    #[serde(rename = "DetachKeys")]
+    pub detach_keys: Option<String>,
+    /// Environment variables in the form `KEY=value`.
+    #[serde(rename = "Env")]
+    pub env: Option<Vec<String>>,
+    #[serde(rename = "Privileged")]
+    pub privileged: Option<bool>,
+    #[serde(rename = "Tty")]
+    pub tty: Option<bool>,
+    #[serde(rename = "User")]
+    pub user: Option<String>,
+    #[serde(rename = "WorkingDir")]
+    pub working_dir: Option<String>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for CreateExecParameter {
+    fn example() -> Self {
+        Self {
+            container_name: String::from("CreateExecParameter"),
+            attach_stderr: Some(true),
+            attach_stdin: Some(false),
+            attach_stdout: Some(true),
+            cmd: Some(vec![
+                String::from("sh"),
+                String::from("-c"),
+                String::from("echo stdout; echo stderr >&2"),
+            ]),
+            detach_keys: None,
+            env: Some(vec![String::from("CREATE_EXEC_PARAMETER=example")]),
+            privileged: Some(false),
+            tty: Some(false),
+            user: None,
+            working_dir: Some(String::from("/")),
+        }
+    }
+}
+
+//json
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct StartExecParameter {
+    #[serde(skip_serializing)]
+    pub exec_id: String,
+    #[serde(rename = "Detach")]
+    pub detach: Option<bool>,
+    /// Has to match the `tty` setting used to create the exec instance, it determines whether the
+    /// output is multiplexed into stdout and stderr.
+    #[serde(rename = "Tty")]
+    pub tty: Option<bool>,
+    #[serde(rename = "h")]
+    pub height: Option<u16>,
+    #[serde(rename = "w")]
+    pub width: Option<u16>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for StartExecParameter {
+    fn example() -> Self {
+        Self {
+            exec_id: String::from("StartExecParameter"),
+            detach: Some(false),
+            tty: Some(false),
+            height: None,
+            width: None,
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct InspectExecParameter {
+    #[serde(skip_serializing)]
+    pub exec_id: String,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for InspectExecParameter {
+    fn example() -> Self {
+        Self {
+            exec_id: String::from("InspectExecParameter"),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ResizeExecParameter {
+    #[serde(skip_serializing)]
+    pub exec_id: String,
+    #[serde(rename = "h")]
+    pub height: Option<u16>,
+    #[serde(rename = "w")]
+    pub width: Option<u16>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ResizeExecParameter {
+    fn example() -> Self {
+        Self {
+            exec_id: String::from("ResizeExecParameter"),
+            height: Some(24),
+            width: Some(80),
+        }
+    }
+}
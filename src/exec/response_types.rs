@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CreateExecResponse {
+    #[serde(rename = "Id")]
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InspectExecResponse {
+    #[serde(rename = "CanRemove")]
+    pub can_remove: Option<bool>,
+    #[serde(rename = "ContainerID")]
+    pub container_id: Option<String>,
+    #[serde(rename = "DetachKeys")]
+    pub detach_keys: Option<String>,
+    #[serde(rename = "ExitCode")]
+    pub exit_code: Option<i32>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    #[serde(rename = "OpenStderr")]
+    pub open_stderr: Option<bool>,
+    #[serde(rename = "OpenStdin")]
+    pub open_stdin: Option<bool>,
+    #[serde(rename = "OpenStdout")]
+    pub open_stdout: Option<bool>,
+    #[serde(rename = "Pid")]
+    pub pid: Option<i64>,
+    #[serde(rename = "ProcessConfig")]
+    pub process_config: Option<InspectExecProcess>,
+    #[serde(rename = "Running")]
+    pub running: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InspectExecProcess {
+    pub arguments: Option<Vec<String>>,
+    pub entrypoint: Option<String>,
+    pub privileged: Option<bool>,
+    pub tty: Option<bool>,
+    pub user: Option<String>,
+}
+
+/// Output of an exec instance started via `Exec::start`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ExecOutput {
+    Stdout(Vec<u8>),
+    Stderr(Vec<u8>),
+    /// Last item of the stream once podman closed the output. `None` if the exec instance is still
+    /// running.
+    ExitCode(Option<i32>),
+}
@@ -1,13 +1,15 @@
+use crate::containers::response_types::FilesystemChange;
 use crate::error::{PodtenderError, Result};
+use crate::images::layer_usage::{self, ImageLayerUsage, ImageLayers};
 use crate::images::parameter_types::*;
+use crate::images::registry_auth;
 use crate::images::response_types::*;
 use crate::podman_service::PodmanService;
 use crate::utils;
-use futures::Stream;
+use futures::{Stream, TryStreamExt};
 use hyper::body::Bytes;
-use std::collections::HashMap;
 use std::convert::TryInto;
-#[cfg(feature = "enable-tracing")]
+#[cfg(feature = "tracing")]
 use tracing::instrument;
 
 /// Container operations.
@@ -22,9 +24,9 @@ impl<'service> Images<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageDeleteLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.remove"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.remove"))]
     pub async fn remove(&self, parameter: RemoveImageParameter) -> Result<RemoveImageResponse> {
-        let endpoint = utils::create_endpoint(&format!("/libpod/images/{}", parameter.image_name));
+        let endpoint = format!("/libpod/images/{}", parameter.image_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -33,11 +35,22 @@ impl<'service> Images<'service> {
         utils::deserialize_service_response(service_response)
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageChangesLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.changes"))]
+    pub async fn changes(&self, parameter: ImageChangesParameter) -> Result<Vec<FilesystemChange>> {
+        let endpoint = format!("/libpod/images/{}/changes", parameter.image_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageExistsLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.exists"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.exists"))]
     pub async fn exists(&self, parameter: ImageExistsParameter) -> Result<()> {
-        let endpoint =
-            utils::create_endpoint(&format!("/libpod/images/{}/exists", parameter.image_name));
+        let endpoint = format!("/libpod/images/{}/exists", parameter.image_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -46,16 +59,12 @@ impl<'service> Images<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageGetLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.export"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.export"))]
     pub async fn export(
         &self,
         parameter: ExportImageParameter,
     ) -> Result<impl Stream<Item = Result<Vec<u8>>>> {
-        let endpoint = format!(
-            "{}{}/get",
-            utils::create_endpoint("/libpod/images/"),
-            parameter.image_name
-        );
+        let endpoint = format!("/libpod/images/{}/get", parameter.image_name);
 
         let (_status_code, result_stream, _header_map) = self
             .podman_service
@@ -64,14 +73,24 @@ impl<'service> Images<'service> {
         Ok(result_stream)
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageHistoryLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.history"))]
+    pub async fn history(
+        &self,
+        parameter: ImageHistoryParameter,
+    ) -> Result<Vec<ImageHistoryEntry>> {
+        let endpoint = format!("/libpod/images/{}/history", parameter.image_name);
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, None, None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageInspectLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.inspect"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.inspect"))]
     pub async fn inspect(&self, parameter: InspectImageParameter) -> Result<InspectImageResponse> {
-        let endpoint = format!(
-            "{}{}/json",
-            utils::create_endpoint("/libpod/images/"),
-            parameter.image_name
-        );
+        let endpoint = format!("/libpod/images/{}/json", parameter.image_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -80,57 +99,64 @@ impl<'service> Images<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageListLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.list"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.list"))]
     pub async fn list(
         &self,
         parameter: ListImagesParameter,
     ) -> Result<Vec<ListImagesResponseEntry>> {
-        let endpoint = utils::create_endpoint("/libpod/images/json");
+        let endpoint = "/libpod/images/json";
         let parameter: ListImagesParameterQuery = parameter.try_into()?;
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .get_request(&endpoint, Some(query), None, None)
+            .get_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImagePruneLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.prune"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.prune"))]
     pub async fn prune(
         &self,
         parameter: PruneImagesParameter,
     ) -> Result<Vec<PruneImagesResponseEntry>> {
-        let endpoint = utils::create_endpoint("/libpod/images/prune");
+        let endpoint = "/libpod/images/prune";
         let parameter: PruneImagesParameterQuery = parameter.try_into()?;
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, Some(query), None, None)
+            .post_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImagePullLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.pull"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.pull"))]
     pub async fn pull(
         &self,
         parameter: PullImagesParameter,
     ) -> Result<impl Stream<Item = Result<PullImagesResponse>>> {
-        let endpoint = utils::create_endpoint("/libpod/images/pull");
-
-        let header = {
-            if parameter.x_registry_auth_header.is_some() {
-                let mut header_map = HashMap::new();
-                header_map.insert(
-                    String::from("X-Registry-Auth"),
-                    parameter.x_registry_auth_header.as_ref().unwrap().clone(),
-                );
-                Some(header_map)
-            } else {
-                None
-            }
-        };
+        let endpoint = "/libpod/images/pull";
+
+        let header = registry_auth::create_header(parameter.registry_auth.as_ref())?;
+        let query = serde_qs::to_string(&parameter)?;
+
+        let (_status_code, result_stream, _header_map) = self
+            .podman_service
+            .post_json_stream(endpoint, Some(query), header, None)
+            .await?;
+        Ok(result_stream)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImagePushLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.push"))]
+    pub async fn push(
+        &self,
+        parameter: PushImageParameter,
+    ) -> Result<impl Stream<Item = Result<PushImageResponse>>> {
+        let endpoint = format!("/libpod/images/{}/push", parameter.image_name);
+
+        let header = registry_auth::create_header(parameter.registry_auth.as_ref())?;
         let query = serde_qs::to_string(&parameter)?;
 
         let (_status_code, result_stream, _header_map) = self
@@ -140,25 +166,125 @@ impl<'service> Images<'service> {
         Ok(result_stream)
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageTagLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.tag"))]
+    pub async fn tag(&self, parameter: TagImageParameter) -> Result<()> {
+        let endpoint = format!("/libpod/images/{}/tag", parameter.image_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageTreeLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.tree"))]
+    pub async fn tree(&self, parameter: ImageTreeParameter) -> Result<ImageTreeResponse> {
+        let endpoint = format!("/libpod/images/{}/tree", parameter.image_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// Computes which part of each listed image's size is shared with other images and which is
+    /// unique to the image, i.e. freed when removing it. See [`layer_usage`] for the analysis.
+    ///
+    /// Shared layers are counted across all local images, `parameter.filters` only selects the
+    /// images returned. This costs one inspect request per local image plus one history request
+    /// per returned image, even if the filters select a single image.
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.layer_usage"))]
+    pub async fn layer_usage(
+        &self,
+        parameter: ListImagesParameter,
+    ) -> Result<Vec<ImageLayerUsage>> {
+        let all_entries = self
+            .list(ListImagesParameter {
+                all: parameter.all,
+                filters: None,
+            })
+            .await?;
+        let listed_ids = self
+            .list(parameter)
+            .await?
+            .into_iter()
+            .map(|entry| entry.id.ok_or(PodtenderError::ImageWithoutId))
+            .collect::<Result<Vec<String>>>()?;
+
+        let mut images = vec![];
+        for entry in all_entries {
+            let image_id = entry.id.ok_or(PodtenderError::ImageWithoutId)?;
+            let inspect = self
+                .inspect(InspectImageParameter {
+                    image_name: image_id.clone(),
+                })
+                .await?;
+            let digests = inspect
+                .root_fs
+                .and_then(|root_fs| root_fs.layers)
+                .unwrap_or_default();
+            // Sizes are only needed for the returned images, the digests of the others are
+            // enough to count shared layers.
+            let history = if listed_ids.contains(&image_id) {
+                self.history(ImageHistoryParameter {
+                    image_name: image_id.clone(),
+                })
+                .await?
+            } else {
+                vec![]
+            };
+            let layers = layer_usage::layers_with_sizes(
+                digests,
+                &inspect.history.unwrap_or_default(),
+                &history,
+            );
+            images.push(ImageLayers {
+                image_id,
+                names: entry.names.unwrap_or_default(),
+                layers,
+            });
+        }
+        Ok(layer_usage::analyze_layer_usage(&images)
+            .into_iter()
+            .filter(|usage| listed_ids.contains(&usage.image_id))
+            .collect())
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageUntagLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.untag"))]
+    pub async fn untag(&self, parameter: UntagImageParameter) -> Result<()> {
+        let endpoint = format!("/libpod/images/{}/untag", parameter.image_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageSearchLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Images.search"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Images.search"))]
     pub async fn search(
         &self,
         parameter: SearchImagesParameter,
     ) -> Result<Vec<SearchImagesResponseEntry>> {
-        let endpoint = utils::create_endpoint("/libpod/images/search");
+        let endpoint = "/libpod/images/search";
+        let header = registry_auth::create_header(parameter.registry_auth.as_ref())?;
         let parameter: SearchImagesParameterQuery = parameter.try_into()?;
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .get_request(&endpoint, Some(query), None, None)
+            .get_request(endpoint, Some(query), header, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageImportLibpod>
     #[cfg_attr(
-        feature = "enable-tracing",
+        feature = "tracing",
         instrument(name = "Images.import", skip(file_stream))
     )]
     pub async fn import<S, O, E>(
@@ -171,18 +297,18 @@ impl<'service> Images<'service> {
         O: Into<Bytes> + 'static,
         E: Into<PodtenderError> + Send + 'static,
     {
-        let endpoint = utils::create_endpoint("/libpod/images/import");
+        let endpoint = "/libpod/images/import";
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_send_file_chunks_stream(&endpoint, Some(query), None, Some(file_stream))
+            .post_send_file_chunks_stream(endpoint, Some(query), None, Some(file_stream))
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageLoadLibpod>
     #[cfg_attr(
-        feature = "enable-tracing",
+        feature = "tracing",
         instrument(name = "Images.load", skip(file_stream))
     )]
     pub async fn load<S, O, E>(&self, file_stream: S) -> Result<LoadImageResponse>
@@ -191,12 +317,69 @@ impl<'service> Images<'service> {
         O: Into<Bytes> + 'static,
         E: Into<PodtenderError> + Send + 'static,
     {
-        let endpoint = utils::create_endpoint("/libpod/images/load");
+        let endpoint = "/libpod/images/load";
 
         let service_response = self
             .podman_service
-            .post_send_file_chunks_stream(&endpoint, None, None, Some(file_stream))
+            .post_send_file_chunks_stream(endpoint, None, None, Some(file_stream))
             .await?;
         utils::deserialize_service_response(service_response)
     }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ImageBuildLibpod>
+    /// Builds an image from `context_stream`, a tar archive of the build context containing the
+    /// Containerfile/Dockerfile. The returned stream ends with either
+    /// `BuildImageProgress::ImageId` or `BuildImageProgress::Error`.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(name = "Images.build", skip(context_stream))
+    )]
+    pub async fn build<S, O, E>(
+        &self,
+        parameter: BuildImageParameter,
+        context_stream: S,
+    ) -> Result<impl Stream<Item = Result<BuildImageProgress>>>
+    where
+        S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<PodtenderError> + Send + 'static,
+    {
+        let endpoint = "/libpod/build";
+        let tags = parameter.tags.clone();
+        let header = registry_auth::create_header(parameter.registry_auth.as_ref())?;
+        let parameter: BuildImageParameterQuery = parameter.try_into()?;
+
+        // Start workaround cause podman only supports query arrays in this format:
+        // t=tag1&t=tag2
+        let query = {
+            use serde::Serialize;
+
+            #[derive(Serialize, Debug)]
+            struct TempTag {
+                t: String,
+            }
+            let mut query = serde_qs::to_string(&parameter)?;
+            for t in tags.unwrap_or_default() {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                query.push_str(&serde_qs::to_string(&TempTag { t })?);
+            }
+            query
+        };
+        // end workaround
+
+        let (_status_code, result_stream, _header_map) = self
+            .podman_service
+            .post_send_file_json_stream::<_, _, _, BuildImageResponse>(
+                endpoint,
+                Some(query),
+                header,
+                Some(context_stream),
+            )
+            .await?;
+        Ok(image_id_from_last_line(
+            result_stream.map_ok(BuildImageProgress::from),
+        ))
+    }
 }
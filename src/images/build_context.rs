@@ -0,0 +1,274 @@
+//! Creates the tar archive of a build context used by `Images::build`. Requires the `tar` feature.
+//!
+//! Files matching the rules of a `.containerignore` (or `.dockerignore` if the former doesn't
+//! exist) in the context's root are left out. Supported are the usual rules: `#` comments, `*` and
+//! `?` within a path component, `**` for any number of components, `[...]` character classes and
+//! `!` to include previously excluded paths again. A pattern excluding a directory excludes its
+//! content as well. The last matching pattern wins.
+//!
+//! Entries are added in lexical order with fixed mtimes and owners, the same directory content
+//! always results in the same archive. This keeps builds reproducible and lets podman reuse
+//! cached layers.
+//!
+//! The archive is created in memory with blocking file system io, wrap the calls in
+//! `tokio::task::spawn_blocking` for large contexts.
+
+use crate::error::Result;
+use futures::Stream;
+use hyper::body::Bytes;
+use std::path::Path;
+
+/// Names of the ignore files in order of precedence.
+const IGNORE_FILES: [&str; 2] = [".containerignore", ".dockerignore"];
+
+/// Creates a tar archive of the directory `context_dir` honoring its ignore file.
+pub fn build_context_archive(context_dir: impl AsRef<Path>) -> Result<Vec<u8>> {
+    let context_dir = context_dir.as_ref();
+    let rules = IgnoreRules::from_context_dir(context_dir)?;
+
+    let mut builder = tar::Builder::new(Vec::new());
+    builder.mode(tar::HeaderMode::Deterministic);
+    builder.follow_symlinks(false);
+    append_dir_content(&mut builder, context_dir, "", &rules)?;
+    Ok(builder.into_inner()?)
+}
+
+/// Same as [`build_context_archive`] but returns a stream which can be passed to `Images::build`
+/// directly.
+pub fn build_context_stream(
+    context_dir: impl AsRef<Path>,
+) -> Result<impl Stream<Item = Result<Bytes>>> {
+    let archive = build_context_archive(context_dir)?;
+    Ok(futures::stream::once(futures::future::ok(Bytes::from(
+        archive,
+    ))))
+}
+
+/// Recursively appends the content of `dir` in lexical order. `relative_dir` is the path of `dir`
+/// inside the archive, empty for the context's root.
+fn append_dir_content(
+    builder: &mut tar::Builder<Vec<u8>>,
+    dir: &Path,
+    relative_dir: &str,
+    rules: &IgnoreRules,
+) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        let relative_path = if relative_dir.is_empty() {
+            file_name.to_string()
+        } else {
+            format!("{}/{}", relative_dir, file_name)
+        };
+        let excluded = rules.is_excluded(&relative_path);
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            if excluded && !rules.may_include_content_of(&relative_path) {
+                continue;
+            }
+            if !excluded {
+                builder.append_dir(&relative_path, entry.path())?;
+            }
+            append_dir_content(builder, &entry.path(), &relative_path, rules)?;
+        } else if !excluded {
+            builder.append_path_with_name(entry.path(), &relative_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parsed content of an ignore file.
+#[derive(Debug, Default)]
+struct IgnoreRules {
+    patterns: Vec<IgnorePattern>,
+}
+
+#[derive(Debug)]
+struct IgnorePattern {
+    components: Vec<String>,
+    negated: bool,
+}
+
+impl IgnoreRules {
+    fn from_context_dir(context_dir: &Path) -> Result<Self> {
+        for ignore_file in IGNORE_FILES {
+            let path = context_dir.join(ignore_file);
+            if path.is_file() {
+                return Ok(Self::parse(&std::fs::read_to_string(path)?));
+            }
+        }
+        Ok(Self::default())
+    }
+
+    fn parse(content: &str) -> Self {
+        let patterns = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(line) => (true, line.trim()),
+                    None => (false, line),
+                };
+                let components: Vec<String> = line
+                    .split('/')
+                    .filter(|component| !component.is_empty() && *component != ".")
+                    .map(String::from)
+                    .collect();
+                if components.is_empty() {
+                    None
+                } else {
+                    Some(IgnorePattern {
+                        components,
+                        negated,
+                    })
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// `path` is relative to the context's root and uses `/` as separator.
+    fn is_excluded(&self, path: &str) -> bool {
+        let components: Vec<&str> = path.split('/').collect();
+        let mut excluded = false;
+        for pattern in &self.patterns {
+            // A pattern matching a parent directory matches its content too.
+            let matches = (1..=components.len())
+                .any(|len| match_components(&pattern.components, &components[..len]));
+            if matches {
+                excluded = !pattern.negated;
+            }
+        }
+        excluded
+    }
+
+    /// Whether a negated pattern could include something inside the excluded directory `path`.
+    fn may_include_content_of(&self, path: &str) -> bool {
+        let prefix = format!("{}/", path);
+        self.patterns.iter().any(|pattern| {
+            pattern.negated && {
+                let pattern = pattern.components.join("/");
+                pattern.starts_with(&prefix) || pattern.contains('*') || pattern.contains('?')
+            }
+        })
+    }
+}
+
+/// Matches path components against pattern components, `**` matches any number of components.
+fn match_components(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_components(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((component, path_rest)) => {
+                match_component(first.as_bytes(), component.as_bytes())
+                    && match_components(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Matches a single path component supporting `*`, `?` and `[...]`.
+fn match_component(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some(b'*') => (0..=name.len()).any(|skip| match_component(&pattern[1..], &name[skip..])),
+        Some(b'?') => !name.is_empty() && match_component(&pattern[1..], &name[1..]),
+        Some(b'[') => match (name.first(), pattern.iter().position(|&b| b == b']')) {
+            (Some(&c), Some(end)) if end > 1 => {
+                let class = &pattern[1..end];
+                let (negated, class) = match class.first() {
+                    Some(b'^') | Some(b'!') => (true, &class[1..]),
+                    _ => (false, class),
+                };
+                let mut matched = false;
+                let mut i = 0;
+                while i < class.len() {
+                    if i + 2 < class.len() && class[i + 1] == b'-' {
+                        matched |= class[i] <= c && c <= class[i + 2];
+                        i += 3;
+                    } else {
+                        matched |= class[i] == c;
+                        i += 1;
+                    }
+                }
+                matched != negated && match_component(&pattern[end + 1..], &name[1..])
+            }
+            _ => false,
+        },
+        Some(b'\\') if pattern.len() > 1 => {
+            name.first() == Some(&pattern[1]) && match_component(&pattern[2..], &name[1..])
+        }
+        Some(&b) => name.first() == Some(&b) && match_component(&pattern[1..], &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignore_rules() {
+        let rules = IgnoreRules::parse(
+            "# comment\n\
+             target\n\
+             *.log\n\
+             !keep.log\n\
+             **/*.tmp\n\
+             /docs/[a-c]*.md\n",
+        );
+        assert!(rules.is_excluded("target"));
+        assert!(rules.is_excluded("target/debug/build"));
+        assert!(rules.is_excluded("build.log"));
+        assert!(!rules.is_excluded("keep.log"));
+        assert!(!rules.is_excluded("nested/build.log"));
+        assert!(rules.is_excluded("nested/deeper/file.tmp"));
+        assert!(rules.is_excluded("docs/api.md"));
+        assert!(!rules.is_excluded("docs/readme.md"));
+        assert!(!rules.is_excluded("src/main.rs"));
+    }
+
+    #[test]
+    fn deterministic_archive_without_ignored_files() {
+        let root =
+            std::env::temp_dir().join(format!("podtender_build_context_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("Containerfile"), b"FROM alpine").unwrap();
+        std::fs::write(root.join("src/b.txt"), b"b").unwrap();
+        std::fs::write(root.join("src/a.txt"), b"a").unwrap();
+        std::fs::write(root.join("target/out"), b"out").unwrap();
+        std::fs::write(root.join(".containerignore"), b"target\n").unwrap();
+
+        let archive = build_context_archive(&root).unwrap();
+        let second_archive = build_context_archive(&root).unwrap();
+
+        let mut archive_reader = tar::Archive::new(archive.as_slice());
+        let names: Vec<String> = archive_reader
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(archive, second_archive);
+        assert_eq!(
+            vec![
+                ".containerignore",
+                "Containerfile",
+                "src",
+                "src/a.txt",
+                "src/b.txt"
+            ],
+            names
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Client side analysis of layers shared between images, see `Images::layer_usage`.
+//!
+//! Only the unique size of an image is freed when the image is removed, shared layers stay until
+//! the last image using them is removed.
+
+use crate::images::response_types::{ImageHistoryEntry, ImageLayer};
+use std::collections::HashMap;
+
+/// Layers of an image, input of [`analyze_layer_usage`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImageLayers {
+    pub image_id: String,
+    pub names: Vec<String>,
+    /// Layers from the base layer to the top layer.
+    pub layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Layer {
+    /// Digest of the uncompressed layer as listed in `RootFs::layers`.
+    pub digest: String,
+    /// Uncompressed size in bytes, `None` if the image history doesn't line up with its layers.
+    pub size: Option<i64>,
+}
+
+/// Disk usage of an image split into layers used by other images and layers only used by this
+/// image. Layers of unknown size are left out of the sizes, see `sizes_complete`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImageLayerUsage {
+    pub image_id: String,
+    pub names: Vec<String>,
+    /// Size of the layers only used by this image, freed when the image is removed.
+    pub unique_size: i64,
+    /// Size of the layers used by other images as well.
+    pub shared_size: i64,
+    pub total_size: i64,
+    /// `false` if the size of at least one layer is unknown, the sizes are lower bounds then.
+    pub sizes_complete: bool,
+}
+
+/// Computes the shared and unique layer sizes of `images`. Layers are identified by digest, a
+/// layer listed multiple times by one image is counted once.
+pub fn analyze_layer_usage(images: &[ImageLayers]) -> Vec<ImageLayerUsage> {
+    let mut image_count_per_layer: HashMap<&str, usize> = HashMap::new();
+    for image in images {
+        let mut digests: Vec<&str> = image.layers.iter().map(|l| l.digest.as_str()).collect();
+        digests.sort_unstable();
+        digests.dedup();
+        for digest in digests {
+            *image_count_per_layer.entry(digest).or_default() += 1;
+        }
+    }
+
+    images
+        .iter()
+        .map(|image| {
+            let mut seen = Vec::new();
+            let mut unique_size = 0;
+            let mut shared_size = 0;
+            let mut sizes_complete = true;
+            for layer in &image.layers {
+                if seen.contains(&layer.digest.as_str()) {
+                    continue;
+                }
+                seen.push(layer.digest.as_str());
+                let size = match layer.size {
+                    Some(size) => size,
+                    None => {
+                        sizes_complete = false;
+                        continue;
+                    }
+                };
+                if image_count_per_layer[layer.digest.as_str()] > 1 {
+                    shared_size += size;
+                } else {
+                    unique_size += size;
+                }
+            }
+            ImageLayerUsage {
+                image_id: image.image_id.clone(),
+                names: image.names.clone(),
+                unique_size,
+                shared_size,
+                total_size: unique_size + shared_size,
+                sizes_complete,
+            }
+        })
+        .collect()
+}
+
+/// Pairs the layer digests of `RootFs` with the sizes reported by `Images::history`.
+/// `inspect_history` (oldest first) tells which history entries created a layer, `history`
+/// (newest first) contains the sizes. Sizes are unknown if the histories don't line up with the
+/// layers, e.g. for images without history.
+pub(crate) fn layers_with_sizes(
+    digests: Vec<String>,
+    inspect_history: &[ImageLayer],
+    history: &[ImageHistoryEntry],
+) -> Vec<Layer> {
+    let sizes: Vec<Option<i64>> = if inspect_history.len() == history.len() {
+        inspect_history
+            .iter()
+            .zip(history.iter().rev())
+            .filter(|(layer, _)| !layer.empty_layer.unwrap_or(false))
+            .map(|(_, entry)| entry.size)
+            .collect()
+    } else {
+        vec![]
+    };
+    let sizes = if sizes.len() == digests.len() {
+        sizes
+    } else {
+        vec![None; digests.len()]
+    };
+    digests
+        .into_iter()
+        .zip(sizes)
+        .map(|(digest, size)| Layer { digest, size })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(id: &str, layers: &[(&str, Option<i64>)]) -> ImageLayers {
+        ImageLayers {
+            image_id: String::from(id),
+            names: vec![],
+            layers: layers
+                .iter()
+                .map(|(digest, size)| Layer {
+                    digest: String::from(*digest),
+                    size: *size,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn shared_and_unique_sizes() {
+        let images = vec![
+            image("base", &[("a", Some(100))]),
+            image("app", &[("a", Some(100)), ("b", Some(20))]),
+            image("other", &[("c", Some(5))]),
+        ];
+        let usage: Vec<(i64, i64)> = analyze_layer_usage(&images)
+            .iter()
+            .map(|usage| (usage.unique_size, usage.shared_size))
+            .collect();
+        assert_eq!(vec![(0, 100), (20, 100), (5, 0)], usage);
+    }
+
+    #[test]
+    fn sizes_from_history() {
+        let inspect_history = vec![
+            ImageLayer {
+                author: None,
+                comment: None,
+                created: None,
+                created_by: Some(String::from("ADD rootfs")),
+                empty_layer: None,
+            },
+            ImageLayer {
+                author: None,
+                comment: None,
+                created: None,
+                created_by: Some(String::from("CMD [\"sh\"]")),
+                empty_layer: Some(true),
+            },
+            ImageLayer {
+                author: None,
+                comment: None,
+                created: None,
+                created_by: Some(String::from("COPY run.sh")),
+                empty_layer: None,
+            },
+        ];
+        let history_entry = |size| ImageHistoryEntry {
+            id: None,
+            created: None,
+            created_by: None,
+            size: Some(size),
+            comment: None,
+            tags: None,
+        };
+        let history = vec![history_entry(20), history_entry(0), history_entry(100)];
+        let layers = layers_with_sizes(
+            vec![String::from("a"), String::from("b")],
+            &inspect_history,
+            &history,
+        );
+        assert_eq!(
+            vec![
+                Layer {
+                    digest: String::from("a"),
+                    size: Some(100)
+                },
+                Layer {
+                    digest: String::from("b"),
+                    size: Some(20)
+                }
+            ],
+            layers
+        );
+    }
+
+    #[test]
+    fn unknown_sizes() {
+        let images = vec![
+            image("base", &[("a", None)]),
+            image("app", &[("a", Some(100)), ("b", Some(20))]),
+        ];
+        let usage: Vec<(i64, i64, bool)> = analyze_layer_usage(&images)
+            .iter()
+            .map(|usage| (usage.unique_size, usage.shared_size, usage.sizes_complete))
+            .collect();
+        assert_eq!(vec![(0, 0, false), (20, 100, true)], usage);
+
+        let layers = layers_with_sizes(vec![String::from("a")], &[], &[]);
+        assert_eq!(None, layers[0].size);
+    }
+}
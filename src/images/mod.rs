@@ -1,8 +1,15 @@
 //! <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#tag/images>
 
 mod api_call_functions;
+
+#[cfg(feature = "tar")]
+pub mod build_context;
+/// Client side analysis of layers shared between images.
+pub mod layer_usage;
 /// Parameter types for image operations.
 pub mod parameter_types;
+/// Credentials for registries, sent as `X-Registry-Auth` header.
+pub mod registry_auth;
 /// Response types for image operations.
 pub mod response_types;
 
@@ -1,6 +1,8 @@
+use crate::containers::parameter_types::DiffType;
 use crate::error::PodtenderError;
 #[cfg(any(test, feature = "examples"))]
 use crate::example_values_trait::ExampleValues;
+use crate::images::registry_auth::RegistryAuth;
 use crate::utils;
 #[cfg(feature = "builder")]
 use derive_builder::Builder;
@@ -29,6 +31,31 @@ impl ExampleValues for RemoveImageParameter {
     }
 }
 
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ImageChangesParameter {
+    #[serde(skip_serializing)]
+    pub image_name: String,
+    #[serde(rename = "diffType")]
+    pub diff_type: Option<DiffType>,
+    /// Layer or image to compare against, defaults to the image's parent.
+    pub parent: Option<String>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ImageChangesParameter {
+    fn example() -> Self {
+        Self {
+            image_name: String::from("image_changes_parameter"),
+            diff_type: Some(DiffType::All),
+            parent: None,
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
@@ -69,6 +96,24 @@ impl ExampleValues for ExportImageParameter {
     }
 }
 
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ImageHistoryParameter {
+    #[serde(skip_serializing)]
+    pub image_name: String,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ImageHistoryParameter {
+    fn example() -> Self {
+        Self {
+            image_name: String::from("image_history_parameter"),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
@@ -207,8 +252,9 @@ impl TryFrom<PruneImagesParameter> for PruneImagesParameterQuery {
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
 pub struct PullImagesParameter {
+    /// Sent as `X-Registry-Auth` header.
     #[serde(skip_serializing)]
-    pub x_registry_auth_header: Option<String>,
+    pub registry_auth: Option<RegistryAuth>,
     #[serde(rename = "allTags")]
     pub all_tags: Option<bool>,
     #[serde(rename = "Arch")]
@@ -229,7 +275,7 @@ pub struct PullImagesParameter {
 impl ExampleValues for PullImagesParameter {
     fn example() -> Self {
         Self {
-            x_registry_auth_header: None,
+            registry_auth: None,
             reference: Some(String::from("docker://docker.io/library/hello-world")),
             quiet: Some(false),
             credentials: None,
@@ -243,11 +289,55 @@ impl ExampleValues for PullImagesParameter {
     }
 }
 
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct PushImageParameter {
+    #[serde(skip_serializing)]
+    pub image_name: String,
+    /// Sent as `X-Registry-Auth` header.
+    #[serde(skip_serializing)]
+    pub registry_auth: Option<RegistryAuth>,
+    /// Push all tags of the image.
+    #[serde(rename = "all")]
+    pub all_tags: Option<bool>,
+    /// `gzip`, `zstd` or `zstd:chunked`.
+    #[serde(rename = "compressionFormat")]
+    pub compression_format: Option<String>,
+    /// Where to push the image to, e.g. `docker://registry.example.com/image:latest`. Defaults
+    /// to `image_name`.
+    pub destination: Option<String>,
+    /// Only report errors and the manifest digest.
+    pub quiet: Option<bool>,
+    #[serde(rename = "tlsVerify")]
+    pub tls_verify: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for PushImageParameter {
+    fn example() -> Self {
+        Self {
+            image_name: String::from("push_image_parameter"),
+            registry_auth: None,
+            all_tags: Some(false),
+            compression_format: Some(String::from("gzip")),
+            destination: Some(String::from("oci-archive:/tmp/push_image_parameter.tar")),
+            quiet: Some(false),
+            tls_verify: Some(true),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
 pub struct SearchImagesParameter {
+    /// Sent as `X-Registry-Auth` header.
+    #[serde(skip_serializing)]
+    pub registry_auth: Option<RegistryAuth>,
     pub filters: Option<HashMap<String, Vec<String>>>,
     pub limit: Option<i32>,
     #[serde(rename = "listTags")]
@@ -266,6 +356,7 @@ impl ExampleValues for SearchImagesParameter {
         // podman has started filtering for exactly the specified stars with v4.0.0
         //filter_map.insert(String::from("stars"), vec![String::from("1")]);
         Self {
+            registry_auth: None,
             term: Some(String::from("hello-world")),
             limit: None,
             list_tags: Some(false),
@@ -302,3 +393,178 @@ impl TryFrom<SearchImagesParameter> for SearchImagesParameterQuery {
         })
     }
 }
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct TagImageParameter {
+    #[serde(skip_serializing)]
+    pub image_name: String,
+    /// Repository of the new name, e.g. `localhost/image`.
+    pub repo: String,
+    /// Defaults to `latest`.
+    pub tag: Option<String>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for TagImageParameter {
+    fn example() -> Self {
+        Self {
+            image_name: String::from("testcontainer"),
+            repo: String::from("localhost/tag_image_parameter"),
+            tag: Some(String::from("latest")),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ImageTreeParameter {
+    #[serde(skip_serializing)]
+    pub image_name: String,
+    /// Show the images depending on this image instead of its layers.
+    #[serde(rename = "whatrequires")]
+    pub what_requires: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ImageTreeParameter {
+    fn example() -> Self {
+        Self {
+            image_name: String::from("image_tree_parameter"),
+            what_requires: Some(false),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct UntagImageParameter {
+    #[serde(skip_serializing)]
+    pub image_name: String,
+    /// Repository of the name to remove. All names are removed if `repo` and `tag` aren't set.
+    pub repo: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for UntagImageParameter {
+    fn example() -> Self {
+        Self {
+            image_name: String::from("testcontainer"),
+            repo: Some(String::from("localhost/untag_image_parameter")),
+            tag: Some(String::from("latest")),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct BuildImageParameter {
+    /// Path of the Containerfile/Dockerfile inside the build context, defaults to `Dockerfile`.
+    pub dockerfile: Option<String>,
+    /// Names of the built image, e.g. `localhost/image:latest`.
+    pub tags: Option<Vec<String>>,
+    pub build_args: Option<HashMap<String, String>>,
+    /// Stage of a multi-stage build to build.
+    pub target: Option<String>,
+    pub labels: Option<HashMap<String, String>>,
+    /// Platforms to build for, e.g. `linux/amd64`.
+    pub platform: Option<String>,
+    pub no_cache: Option<bool>,
+    /// Cache intermediate layers.
+    pub layers: Option<bool>,
+    /// `always`, `missing`, `never` or `newer`.
+    pub pull_policy: Option<String>,
+    /// Secrets available to `RUN --mount=type=secret`, e.g. `id=mysecret,src=secret.txt` where
+    /// `src` is a path inside the build context.
+    pub secrets: Option<Vec<String>>,
+    /// Remove intermediate containers after a successful build.
+    pub rm: Option<bool>,
+    /// Credentials for the registries of the base images, sent as `X-Registry-Auth` header.
+    #[serde(skip_serializing)]
+    pub registry_auth: Option<RegistryAuth>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for BuildImageParameter {
+    fn example() -> Self {
+        let mut build_args = HashMap::new();
+        build_args.insert(String::from("PODTENDER"), String::from("build"));
+        let mut labels = HashMap::new();
+        labels.insert(String::from("podtender"), String::from("build"));
+        Self {
+            dockerfile: Some(String::from("Dockerfile")),
+            tags: Some(vec![String::from("localhost/build_image_parameter:latest")]),
+            build_args: Some(build_args),
+            target: None,
+            labels: Some(labels),
+            platform: None,
+            no_cache: Some(false),
+            layers: Some(true),
+            pull_policy: Some(String::from("missing")),
+            secrets: None,
+            rm: Some(true),
+            registry_auth: None,
+        }
+    }
+}
+
+/// Internal representation of `BuildImageParameter` since maps and arrays can't be serialized in a
+/// single step. They need to be serialized to json, then to query. `TryInto` tries to perform the
+/// serialisation into json. Tags are added separately since podman expects them as repeated `t`.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct BuildImageParameterQuery {
+    pub dockerfile: Option<String>,
+    pub buildargs: Option<String>,
+    pub target: Option<String>,
+    pub labels: Option<String>,
+    pub platform: Option<String>,
+    pub nocache: Option<bool>,
+    pub layers: Option<bool>,
+    pub pullpolicy: Option<String>,
+    pub secrets: Option<String>,
+    pub rm: Option<bool>,
+}
+
+impl TryFrom<BuildImageParameter> for BuildImageParameterQuery {
+    type Error = PodtenderError;
+    fn try_from(param: BuildImageParameter) -> Result<Self, Self::Error> {
+        let buildargs = param
+            .build_args
+            .map(|build_args| serde_json::to_string(&build_args))
+            .transpose()?;
+        let labels = param
+            .labels
+            .map(|labels| serde_json::to_string(&labels))
+            .transpose()?;
+        let secrets = param
+            .secrets
+            .map(|secrets| serde_json::to_string(&secrets))
+            .transpose()?;
+        Ok(BuildImageParameterQuery {
+            dockerfile: param.dockerfile,
+            buildargs,
+            target: param.target,
+            labels,
+            platform: param.platform,
+            nocache: param.no_cache,
+            layers: param.layers,
+            pullpolicy: param.pull_policy,
+            secrets,
+            rm: param.rm,
+        })
+    }
+}
@@ -0,0 +1,220 @@
+//! Registry credentials sent to podman as `X-Registry-Auth` header, used by `Images::pull`,
+//! `Images::push`, `Images::search` and `Images::build`.
+
+use crate::error::Result;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Credentials for a registry. `Debug` redacts password and identity token since parameters are
+/// logged with the `tracing` feature.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Default, Clone, Eq, PartialEq)]
+pub struct RegistryCredentials {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Token to authenticate with instead of username and password.
+    #[serde(rename = "identitytoken")]
+    pub identity_token: Option<String>,
+    /// Registry the credentials are meant for, e.g. `quay.io`.
+    #[serde(rename = "serveraddress")]
+    pub server_address: Option<String>,
+}
+
+impl fmt::Debug for RegistryCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = |secret: &Option<String>| secret.as_ref().map(|_| "<redacted>");
+        f.debug_struct("RegistryCredentials")
+            .field("username", &self.username)
+            .field("password", &redacted(&self.password))
+            .field("identity_token", &redacted(&self.identity_token))
+            .field("server_address", &self.server_address)
+            .finish()
+    }
+}
+
+/// Credentials for one or multiple registries.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum RegistryAuth {
+    /// Credentials used for the registry of the requested image.
+    Credentials(RegistryCredentials),
+    /// Credentials per registry, e.g. loaded from an `auth.json`. Needed by builds pulling from
+    /// multiple registries.
+    PerRegistry(HashMap<String, RegistryCredentials>),
+}
+
+/// Content of an `auth.json` or docker `config.json`.
+#[derive(Deserialize, Debug)]
+struct AuthFile {
+    #[serde(default)]
+    auths: HashMap<String, AuthFileEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AuthFileEntry {
+    /// Base64 encoded `username:password`.
+    auth: Option<String>,
+    #[serde(rename = "identitytoken")]
+    identity_token: Option<String>,
+}
+
+impl RegistryAuth {
+    /// Username and password for the registry of the requested image.
+    pub fn password(username: impl Into<String>, password: impl Into<String>) -> Self {
+        RegistryAuth::Credentials(RegistryCredentials {
+            username: Some(username.into()),
+            password: Some(password.into()),
+            ..Default::default()
+        })
+    }
+
+    /// Identity token for the registry of the requested image.
+    pub fn identity_token(identity_token: impl Into<String>) -> Self {
+        RegistryAuth::Credentials(RegistryCredentials {
+            identity_token: Some(identity_token.into()),
+            ..Default::default()
+        })
+    }
+
+    /// Value of the `X-Registry-Auth` header, url safe base64 encoded json.
+    pub fn to_header_value(&self) -> Result<String> {
+        let json = serde_json::to_vec(self)?;
+        Ok(base64::engine::general_purpose::URL_SAFE.encode(json))
+    }
+
+    /// Loads the credentials of all registries from an `auth.json` or docker `config.json`.
+    /// Credential helpers (`credHelpers`, `credsStore`) aren't supported.
+    pub fn from_auth_file(path: impl AsRef<Path>) -> Result<Self> {
+        let auth_file: AuthFile = serde_json::from_slice(&std::fs::read(path)?)?;
+        let mut registries = HashMap::new();
+        for (registry, entry) in auth_file.auths {
+            let mut credentials = RegistryCredentials {
+                identity_token: entry.identity_token,
+                server_address: Some(registry.clone()),
+                ..Default::default()
+            };
+            if let Some(auth) = entry.auth {
+                let auth = base64::engine::general_purpose::STANDARD.decode(auth)?;
+                let auth = String::from_utf8(auth)?;
+                let (username, password) = auth.split_once(':').ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!(
+                            "auth of {} is not in the format username:password",
+                            registry
+                        ),
+                    )
+                })?;
+                credentials.username = Some(username.to_string());
+                credentials.password = Some(password.to_string());
+            }
+            registries.insert(registry, credentials);
+        }
+        Ok(RegistryAuth::PerRegistry(registries))
+    }
+
+    /// Loads the credentials from the first existing auth file, searched in the same order as
+    /// podman: `$REGISTRY_AUTH_FILE`, `$XDG_RUNTIME_DIR/containers/auth.json`,
+    /// `~/.config/containers/auth.json` and `~/.docker/config.json`. Returns `None` if none
+    /// exists.
+    pub fn from_default_auth_files() -> Result<Option<Self>> {
+        for path in default_auth_file_paths() {
+            if path.is_file() {
+                return Self::from_auth_file(path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn default_auth_file_paths() -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Some(path) = std::env::var_os("REGISTRY_AUTH_FILE") {
+        paths.push(PathBuf::from(path));
+    }
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        paths.push(PathBuf::from(runtime_dir).join("containers/auth.json"));
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        let home = PathBuf::from(home);
+        paths.push(home.join(".config/containers/auth.json"));
+        paths.push(home.join(".docker/config.json"));
+    }
+    paths
+}
+
+/// Creates the `X-Registry-Auth` header if `registry_auth` is set.
+pub(crate) fn create_header(
+    registry_auth: Option<&RegistryAuth>,
+) -> Result<Option<HashMap<String, String>>> {
+    registry_auth
+        .map(|registry_auth| {
+            let mut header_map = HashMap::new();
+            header_map.insert(
+                String::from("X-Registry-Auth"),
+                registry_auth.to_header_value()?,
+            );
+            Ok(header_map)
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_value_of_password() {
+        let header = RegistryAuth::password("user", "secret")
+            .to_header_value()
+            .unwrap();
+        let json = base64::engine::general_purpose::URL_SAFE
+            .decode(header)
+            .unwrap();
+        assert_eq!(br#"{"username":"user","password":"secret"}"#.to_vec(), json);
+    }
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let debug = format!("{:?}", RegistryAuth::password("user", "secret"));
+        assert!(debug.contains("user"));
+        assert!(!debug.contains("secret"));
+    }
+
+    #[test]
+    fn load_auth_file() {
+        let path = std::env::temp_dir().join(format!("podtender_auth_{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            br#"{"auths": {"quay.io": {"auth": "dXNlcjpzZWM6cmV0"}, "ghcr.io": {"identitytoken": "token"}}}"#,
+        )
+        .unwrap();
+        let registry_auth = RegistryAuth::from_auth_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut expected = HashMap::new();
+        expected.insert(
+            String::from("quay.io"),
+            RegistryCredentials {
+                username: Some(String::from("user")),
+                password: Some(String::from("sec:ret")),
+                identity_token: None,
+                server_address: Some(String::from("quay.io")),
+            },
+        );
+        expected.insert(
+            String::from("ghcr.io"),
+            RegistryCredentials {
+                username: None,
+                password: None,
+                identity_token: Some(String::from("token")),
+                server_address: Some(String::from("ghcr.io")),
+            },
+        );
+        assert_eq!(RegistryAuth::PerRegistry(expected), registry_auth.unwrap());
+    }
+}
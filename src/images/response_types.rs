@@ -1,5 +1,7 @@
 use crate::containers::parameter_types::Schema2HealthConfig;
 use crate::containers::response_types::DriverData;
+use crate::error::Result;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -109,6 +111,34 @@ pub struct RootFs {
     pub type_: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ImageHistoryEntry {
+    /// Id of the layer's image, `<missing>` for layers without image.
+    #[serde(rename = "Id")]
+    pub id: Option<String>,
+    /// Unix timestamp.
+    #[serde(rename = "Created")]
+    pub created: Option<i64>,
+    /// Instruction that created the layer.
+    #[serde(rename = "CreatedBy")]
+    pub created_by: Option<String>,
+    #[serde(rename = "Size")]
+    pub size: Option<i64>,
+    #[serde(rename = "Comment")]
+    pub comment: Option<String>,
+    #[serde(rename = "Tags")]
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ImageTreeResponse {
+    /// Human readable tree of the image's layers, as printed by `podman image tree`.
+    #[serde(rename = "Tree")]
+    pub tree: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ImportImageResponse {
@@ -180,6 +210,100 @@ pub struct PullImagesResponse {
     pub stream: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PushImageResponse {
+    pub error: Option<String>,
+    /// Digest of the pushed manifest, sent once the push succeeded.
+    #[serde(rename = "manifestdigest")]
+    pub manifest_digest: Option<String>,
+    pub stream: Option<String>,
+}
+
+/// Raw message of the build output, see `BuildImageProgress` for the interpreted version.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BuildImageResponse {
+    pub stream: Option<String>,
+    pub aux: Option<BuildImageAux>,
+    pub error: Option<String>,
+    #[serde(rename = "errorDetail")]
+    pub error_detail: Option<BuildImageError>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BuildImageAux {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BuildImageError {
+    pub code: Option<i64>,
+    pub message: Option<String>,
+}
+
+/// Progress of a build started via `Images::build`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BuildImageProgress {
+    /// Build output, e.g. `STEP 1/2: FROM alpine`.
+    Stream(String),
+    /// Id of the built image, sent once the build succeeded.
+    ImageId(String),
+    /// The build failed, no further messages follow.
+    Error(BuildImageError),
+}
+
+impl From<BuildImageResponse> for BuildImageProgress {
+    fn from(response: BuildImageResponse) -> Self {
+        if response.error.is_some() || response.error_detail.is_some() {
+            let mut error = response.error_detail.unwrap_or(BuildImageError {
+                code: None,
+                message: None,
+            });
+            if error.message.is_none() {
+                error.message = response.error;
+            }
+            return BuildImageProgress::Error(error);
+        }
+        if let Some(id) = response.aux.and_then(|aux| aux.id) {
+            return BuildImageProgress::ImageId(id);
+        }
+        BuildImageProgress::Stream(response.stream.unwrap_or_default())
+    }
+}
+
+/// The libpod endpoint reports the id of the built image as last line of the output. Turns that
+/// line into `BuildImageProgress::ImageId` if nothing follows it, lines looking like an id are
+/// held back until the next item arrives.
+pub(crate) fn image_id_from_last_line<S>(
+    progress: S,
+) -> impl Stream<Item = Result<BuildImageProgress>>
+where
+    S: Stream<Item = Result<BuildImageProgress>>,
+{
+    futures::stream::unfold(Box::pin(progress.peekable()), |mut progress| async move {
+        let item = match progress.next().await? {
+            Ok(BuildImageProgress::Stream(line)) if is_image_id(&line) => {
+                if progress.as_mut().peek().await.is_none() {
+                    Ok(BuildImageProgress::ImageId(line.trim_end().to_string()))
+                } else {
+                    Ok(BuildImageProgress::Stream(line))
+                }
+            }
+            item => item,
+        };
+        Some((item, progress))
+    })
+}
+
+fn is_image_id(line: &str) -> bool {
+    let trimmed = line.trim_end();
+    trimmed.len() == 64 && trimmed.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct SearchImagesResponseEntry {
@@ -198,3 +322,89 @@ pub struct SearchImagesResponseEntry {
     #[serde(rename = "Tag")]
     pub tag: Option<String>,
 }
+
+#[cfg(test)]
+mod build_image_progress {
+    use super::*;
+
+    fn response(stream: Option<&str>) -> BuildImageResponse {
+        BuildImageResponse {
+            stream: stream.map(String::from),
+            aux: None,
+            error: None,
+            error_detail: None,
+        }
+    }
+
+    #[test]
+    fn output_line() {
+        let progress = BuildImageProgress::from(response(Some("STEP 1/2: FROM alpine\n")));
+        assert_eq!(
+            BuildImageProgress::Stream(String::from("STEP 1/2: FROM alpine\n")),
+            progress
+        );
+    }
+
+    async fn progress(lines: &[&str]) -> Vec<BuildImageProgress> {
+        let responses = lines
+            .iter()
+            .map(|line| Ok(BuildImageProgress::from(response(Some(line)))))
+            .collect::<Vec<_>>();
+        image_id_from_last_line(futures::stream::iter(responses))
+            .map(|item| item.unwrap())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn image_id_line() {
+        let id = "a".repeat(64);
+        let line = format!("{}\n", id);
+        assert_eq!(
+            vec![
+                BuildImageProgress::Stream(String::from("STEP 1/2: FROM alpine\n")),
+                BuildImageProgress::ImageId(id),
+            ],
+            progress(&["STEP 1/2: FROM alpine\n", &line]).await
+        );
+    }
+
+    #[tokio::test]
+    async fn image_id_like_line_mid_stream() {
+        let line = format!("{}\n", "b".repeat(64));
+        assert_eq!(
+            vec![
+                BuildImageProgress::Stream(line.clone()),
+                BuildImageProgress::Stream(String::from("STEP 2/2: RUN true\n")),
+            ],
+            progress(&[&line, "STEP 2/2: RUN true\n"]).await
+        );
+    }
+
+    #[test]
+    fn aux_image_id() {
+        let id = "c".repeat(64);
+        let progress = BuildImageProgress::from(BuildImageResponse {
+            aux: Some(BuildImageAux {
+                id: Some(id.clone()),
+            }),
+            ..response(None)
+        });
+        assert_eq!(BuildImageProgress::ImageId(id), progress);
+    }
+
+    #[test]
+    fn error_message() {
+        let progress = BuildImageProgress::from(BuildImageResponse {
+            error: Some(String::from("build failed")),
+            ..response(None)
+        });
+        assert_eq!(
+            BuildImageProgress::Error(BuildImageError {
+                code: None,
+                message: Some(String::from("build failed")),
+            }),
+            progress
+        );
+    }
+}
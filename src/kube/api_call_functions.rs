@@ -0,0 +1,125 @@
+use crate::error::Result;
+use crate::images::registry_auth;
+use crate::kube::parameter_types::*;
+use crate::kube::response_types::*;
+use crate::podman_service::PodmanService;
+use crate::utils;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+
+/// Kubernetes YAML operations.
+#[derive(Debug)]
+pub struct Kube<'service> {
+    podman_service: &'service PodmanService,
+}
+
+impl<'service> Kube<'service> {
+    pub(crate) fn new(podman_service: &'service PodmanService) -> Self {
+        Kube { podman_service }
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PlayKubeLibpod>
+    /// Creates the pods, containers, volumes and secrets described by `kube_yaml`, which may
+    /// contain multiple YAML documents.
+    #[cfg_attr(feature = "tracing", instrument(name = "Kube.play", skip(kube_yaml)))]
+    pub async fn play(
+        &self,
+        parameter: PlayKubeParameter,
+        kube_yaml: String,
+    ) -> Result<PlayKubeReport> {
+        let endpoint = "/libpod/play/kube";
+        let header = registry_auth::create_header(parameter.registry_auth.as_ref())?;
+
+        // Start workaround cause podman only supports query arrays in this format:
+        // network=network1&network=network2
+        let query = {
+            let mut query = serde_qs::to_string(&PlayKubeParameterQuery::try_from(&parameter)?)?;
+            let arrays = [
+                ("logOptions", &parameter.log_options),
+                ("network", &parameter.network),
+                ("publishPorts", &parameter.publish_ports),
+                ("staticIPs", &parameter.static_ips),
+                ("staticMACs", &parameter.static_macs),
+            ];
+            for (key, values) in arrays {
+                for value in values.iter().flatten() {
+                    let mut temp = HashMap::new();
+                    temp.insert(key, value);
+                    if !query.is_empty() {
+                        query.push('&');
+                    }
+                    query.push_str(&serde_qs::to_string(&temp)?);
+                }
+            }
+            query
+        };
+        // end workaround
+
+        // Like the podman cli, send the config maps as additional documents of the YAML.
+        let mut body = kube_yaml;
+        for config_map in parameter.config_maps.unwrap_or_default() {
+            body.push_str("\n---\n");
+            body.push_str(&config_map);
+        }
+
+        let service_response = self
+            .podman_service
+            .post_request(endpoint, Some(query), header, Some(body))
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PlayKubeDownLibpod>
+    /// Stops and removes the pods described by `kube_yaml`, usually the YAML passed to
+    /// [`Kube::play`].
+    #[cfg_attr(feature = "tracing", instrument(name = "Kube.down", skip(kube_yaml)))]
+    pub async fn down(
+        &self,
+        parameter: KubeDownParameter,
+        kube_yaml: String,
+    ) -> Result<PlayKubeReport> {
+        let endpoint = "/libpod/play/kube";
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .delete_request(endpoint, Some(query), None, Some(kube_yaml))
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/GenerateKubeLibpod>
+    /// Returns the generated YAML.
+    #[cfg_attr(feature = "tracing", instrument(name = "Kube.generate"))]
+    pub async fn generate(&self, parameter: GenerateKubeParameter) -> Result<String> {
+        let endpoint = "/libpod/generate/kube";
+
+        // Start workaround cause podman only supports query arrays in this format:
+        // names=name1&names=name2
+        let query = {
+            use serde::Serialize;
+
+            #[derive(Serialize, Debug)]
+            struct TempNames<'a> {
+                names: &'a str,
+            }
+            let mut query = serde_qs::to_string(&parameter)?;
+            for names in &parameter.names {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                query.push_str(&serde_qs::to_string(&TempNames { names })?);
+            }
+            query
+        };
+        // end workaround
+
+        let service_response = self
+            .podman_service
+            .get_request(endpoint, Some(query), None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response.clone())?;
+        Ok(String::from_utf8(service_response.body.to_vec())?)
+    }
+}
@@ -0,0 +1,12 @@
+//! <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#tag/containers>
+//!
+//! Kubernetes YAML operations, listed by podman under containers and pods.
+
+mod api_call_functions;
+
+/// Parameter types for kube operations.
+pub mod parameter_types;
+/// Response types for kube operations.
+pub mod response_types;
+
+pub use api_call_functions::*;
@@ -0,0 +1,175 @@
+use crate::error::PodtenderError;
+#[cfg(any(test, feature = "examples"))]
+use crate::example_values_trait::ExampleValues;
+use crate::images::registry_auth::RegistryAuth;
+#[cfg(feature = "builder")]
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct PlayKubeParameter {
+    /// Annotations added to the created pods.
+    pub annotations: Option<HashMap<String, String>>,
+    /// Config maps referenced by the YAML, each a YAML document of kind `ConfigMap`. They are
+    /// sent along with the YAML.
+    pub config_maps: Option<Vec<String>>,
+    pub log_driver: Option<String>,
+    pub log_options: Option<Vec<String>>,
+    /// Networks the pods join, e.g. `bridge` or the name of a network.
+    pub network: Option<Vec<String>>,
+    /// Don't create `/etc/hosts` in the containers.
+    pub no_hosts: Option<bool>,
+    /// Ports to publish on the host in addition to `hostPort`s of the YAML, e.g. `8080:80`.
+    pub publish_ports: Option<Vec<String>>,
+    /// Tear down pods and containers of the YAML which already exist before creating them.
+    pub replace: Option<bool>,
+    /// Start a service container tracking the lifetime of the pods.
+    pub service_container: Option<bool>,
+    /// Start the pods after creating them, podman defaults to `true`.
+    pub start: Option<bool>,
+    pub static_ips: Option<Vec<String>>,
+    pub static_macs: Option<Vec<String>>,
+    #[serde(rename = "tlsVerify")]
+    pub tls_verify: Option<bool>,
+    /// User namespace of the pods, e.g. `auto`.
+    pub userns: Option<String>,
+    /// Wait for the pods to exit and remove them afterwards.
+    pub wait: Option<bool>,
+    /// Credentials to pull the images of the YAML, sent as `X-Registry-Auth` header.
+    #[serde(skip_serializing)]
+    pub registry_auth: Option<RegistryAuth>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for PlayKubeParameter {
+    fn example() -> Self {
+        let mut annotations = HashMap::new();
+        annotations.insert(String::from("podtender"), String::from("play"));
+        Self {
+            annotations: Some(annotations),
+            config_maps: None,
+            log_driver: None,
+            log_options: None,
+            network: None,
+            no_hosts: Some(false),
+            publish_ports: None,
+            replace: Some(true),
+            service_container: None,
+            start: Some(true),
+            static_ips: None,
+            static_macs: None,
+            tls_verify: Some(true),
+            userns: None,
+            wait: None,
+            registry_auth: None,
+        }
+    }
+}
+
+/// Internal representation of `PlayKubeParameter` since annotations need to be serialized to
+/// json, then to query. `TryInto` tries to perform the serialisation into json. Arrays are added
+/// separately since podman expects them as repeated keys.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct PlayKubeParameterQuery {
+    pub annotations: Option<String>,
+    #[serde(rename = "logDriver")]
+    pub log_driver: Option<String>,
+    #[serde(rename = "noHosts")]
+    pub no_hosts: Option<bool>,
+    pub replace: Option<bool>,
+    #[serde(rename = "serviceContainer")]
+    pub service_container: Option<bool>,
+    pub start: Option<bool>,
+    #[serde(rename = "tlsVerify")]
+    pub tls_verify: Option<bool>,
+    pub userns: Option<String>,
+    pub wait: Option<bool>,
+}
+
+impl TryFrom<&PlayKubeParameter> for PlayKubeParameterQuery {
+    type Error = PodtenderError;
+    fn try_from(param: &PlayKubeParameter) -> Result<Self, Self::Error> {
+        let annotations = param
+            .annotations
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        Ok(PlayKubeParameterQuery {
+            annotations,
+            log_driver: param.log_driver.clone(),
+            no_hosts: param.no_hosts,
+            replace: param.replace,
+            service_container: param.service_container,
+            start: param.start,
+            tls_verify: param.tls_verify,
+            userns: param.userns.clone(),
+            wait: param.wait,
+        })
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct KubeDownParameter {
+    /// Remove the volumes created by the YAML as well.
+    pub force: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for KubeDownParameter {
+    fn example() -> Self {
+        Self { force: Some(true) }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct GenerateKubeParameter {
+    /// Names or ids of the containers, pods or volumes to include.
+    #[serde(skip_serializing)]
+    pub names: Vec<String>,
+    /// Generate a kubernetes service object as well.
+    pub service: Option<bool>,
+    /// Kind of the generated object.
+    #[serde(rename = "type")]
+    pub kind: Option<GenerateKubeKind>,
+    /// Replicas of a generated deployment.
+    pub replicas: Option<u32>,
+    /// Don't truncate annotations to the kubernetes limit of 63 characters.
+    #[serde(rename = "noTrunc")]
+    pub no_trunc: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for GenerateKubeParameter {
+    fn example() -> Self {
+        Self {
+            names: vec![String::from("generate_kube_parameter")],
+            service: Some(false),
+            kind: Some(GenerateKubeKind::Pod),
+            replicas: None,
+            no_trunc: Some(false),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GenerateKubeKind {
+    Pod,
+    Deployment,
+}
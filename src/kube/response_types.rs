@@ -0,0 +1,93 @@
+use crate::pods::response_types::ErrIdResponse;
+use crate::secrets::response_types::CreateSecretResponse;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Result of playing or tearing down a kubernetes YAML. Playing fills `pods`, `volumes` and
+/// `secrets`, tearing down the reports of stopped and removed objects.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlayKubeReport {
+    #[serde(rename = "Pods")]
+    pub pods: Option<Vec<PlayKubePod>>,
+    #[serde(rename = "Volumes")]
+    pub volumes: Option<Vec<PlayKubeVolume>>,
+    #[serde(rename = "Secrets")]
+    pub secrets: Option<Vec<PlayKubeSecret>>,
+    #[serde(rename = "StopReport")]
+    pub stop_report: Option<Vec<PlayKubeStoppedPod>>,
+    #[serde(rename = "RmReport")]
+    pub rm_report: Option<Vec<PlayKubeRemovedPod>>,
+    #[serde(rename = "VolumeRmReport")]
+    pub volume_rm_report: Option<Vec<ErrIdResponse>>,
+    #[serde(rename = "SecretRmReport")]
+    pub secret_rm_report: Option<Vec<PlayKubeRemovedSecret>>,
+    #[serde(rename = "ServiceContainerID")]
+    pub service_container_id: Option<String>,
+    /// Exit code of the pods, only set with `PlayKubeParameter::wait`.
+    #[serde(rename = "ExitCode")]
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlayKubePod {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    /// Ids of the created containers.
+    #[serde(rename = "Containers")]
+    pub containers: Option<Vec<String>>,
+    #[serde(rename = "InitContainers")]
+    pub init_containers: Option<Vec<String>>,
+    #[serde(rename = "Logs")]
+    pub logs: Option<Vec<String>>,
+    /// Errors of containers which failed to start.
+    #[serde(rename = "ContainerErrors")]
+    pub container_errors: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlayKubeVolume {
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlayKubeSecret {
+    #[serde(rename = "CreateReport")]
+    pub create_report: Option<CreateSecretResponse>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlayKubeStoppedPod {
+    #[serde(rename = "Errs")]
+    pub errs: Option<Vec<String>>,
+    #[serde(rename = "Id")]
+    pub id: Option<String>,
+    #[serde(rename = "RawInput")]
+    pub raw_input: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlayKubeRemovedPod {
+    /// Removed containers and the error of their removal.
+    #[serde(rename = "RemovedCtrs")]
+    pub removed_ctrs: Option<HashMap<String, Option<String>>>,
+    #[serde(rename = "Err")]
+    pub err: Option<String>,
+    #[serde(rename = "Id")]
+    pub id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlayKubeRemovedSecret {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    #[serde(rename = "Err")]
+    pub err: Option<String>,
+}
@@ -2,18 +2,24 @@
 #![doc = include_str!("../README.md")]
 pub mod containers;
 pub mod error;
+pub mod exec;
 pub mod images;
+pub mod kube;
+pub mod manifests;
 pub mod networks;
 pub mod podman_service;
 pub mod pods;
 pub mod podtender_errors;
+pub mod secrets;
 pub mod system;
 mod utils;
 pub mod volumes;
 
+use podman_service::ApiVersion;
+
 #[cfg(any(test, feature = "examples"))]
 pub mod example_values_trait;
 
-/// The currently targeted podman api version. Will be used in the requests to the api as follows:
-/// `http://d/{PODMAN_API_VERSION}/libpod/...`
-static PODMAN_API_VERSION: &str = "/v4.4.0";
+/// The newest podman api version podtender targets. Requests use the api version of the service
+/// if it's older: `http://d/v{min(service api version, PODMAN_API_VERSION)}/libpod/...`
+const PODMAN_API_VERSION: ApiVersion = ApiVersion::new(4, 5, 0);
@@ -0,0 +1,183 @@
+use crate::error::Result;
+use crate::images::registry_auth;
+use crate::images::response_types::RemoveImageResponse;
+use crate::manifests::parameter_types::*;
+use crate::manifests::response_types::*;
+use crate::podman_service::PodmanService;
+use crate::utils;
+use futures::Stream;
+use serde::Serialize;
+use std::collections::HashMap;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+
+/// Manifest list operations.
+#[derive(Debug)]
+pub struct Manifests<'service> {
+    podman_service: &'service PodmanService,
+}
+
+impl<'service> Manifests<'service> {
+    pub(crate) fn new(podman_service: &'service PodmanService) -> Self {
+        Manifests { podman_service }
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestCreateLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.create"))]
+    pub async fn create(
+        &self,
+        parameter: CreateManifestParameter,
+    ) -> Result<CreateManifestResponse> {
+        let endpoint = format!("/libpod/manifests/{}", parameter.manifest_name);
+
+        // Start workaround cause podman only supports query arrays in this format:
+        // images=image1&images=image2
+        let query = {
+            #[derive(Serialize, Debug)]
+            struct TempImages {
+                images: String,
+            }
+            let mut query = serde_qs::to_string(&parameter)?;
+            for images in parameter.images.unwrap_or_default() {
+                query.push('&');
+                query.push_str(&serde_qs::to_string(&TempImages { images })?);
+            }
+            query
+        };
+        // end workaround
+
+        let service_response = self
+            .podman_service
+            .post_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestInspectLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.inspect"))]
+    pub async fn inspect(
+        &self,
+        parameter: InspectManifestParameter,
+    ) -> Result<InspectManifestResponse> {
+        let endpoint = format!("/libpod/manifests/{}/json", parameter.manifest_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestExistsLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.exists"))]
+    pub async fn exists(&self, parameter: ManifestExistsParameter) -> Result<()> {
+        let endpoint = format!("/libpod/manifests/{}/exists", parameter.manifest_name);
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, None, None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestDeleteLibpod>
+    /// Deletes the manifest list only, the images of its instances are kept.
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.delete"))]
+    pub async fn delete(&self, parameter: DeleteManifestParameter) -> Result<RemoveImageResponse> {
+        let endpoint = format!("/libpod/manifests/{}", parameter.manifest_name);
+        let service_response = self
+            .podman_service
+            .delete_request(&endpoint, None, None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestModifyLibpod>
+    /// Adds images to the manifest list, with operation `update`.
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.add"))]
+    pub async fn add(&self, parameter: AddToManifestParameter) -> Result<ModifyManifestResponse> {
+        let header = registry_auth::create_header(parameter.registry_auth.as_ref())?;
+        let body = ModifyManifestBody {
+            operation: "update",
+            options: &parameter,
+            images: None,
+        };
+        self.modify(
+            &parameter.manifest_name,
+            parameter.tls_verify,
+            header,
+            &body,
+        )
+        .await
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestModifyLibpod>
+    /// Removes an instance from the manifest list, with operation `remove`.
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.remove"))]
+    pub async fn remove(
+        &self,
+        parameter: RemoveFromManifestParameter,
+    ) -> Result<ModifyManifestResponse> {
+        let body = ModifyManifestBody {
+            operation: "remove",
+            options: &(),
+            images: Some(vec![parameter.digest.as_str()]),
+        };
+        self.modify(&parameter.manifest_name, None, None, &body)
+            .await
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestModifyLibpod>
+    /// Sets the annotations and platform of an instance, with operation `annotate`.
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.annotate"))]
+    pub async fn annotate(
+        &self,
+        parameter: AnnotateManifestParameter,
+    ) -> Result<ModifyManifestResponse> {
+        let body = ModifyManifestBody {
+            operation: "annotate",
+            options: &parameter,
+            images: Some(vec![parameter.digest.as_str()]),
+        };
+        self.modify(&parameter.manifest_name, None, None, &body)
+            .await
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/ManifestPushLibpod>
+    /// Returns the progress of the push, the last message contains the digest of the manifest
+    /// list.
+    #[cfg_attr(feature = "tracing", instrument(name = "Manifests.push"))]
+    pub async fn push(
+        &self,
+        parameter: PushManifestParameter,
+    ) -> Result<impl Stream<Item = Result<PushManifestResponse>>> {
+        let endpoint = format!(
+            "/libpod/manifests/{}/registry/{}",
+            parameter.manifest_name, parameter.destination
+        );
+        let header = registry_auth::create_header(parameter.registry_auth.as_ref())?;
+        let query = serde_qs::to_string(&parameter)?;
+
+        let (_status_code, result_stream, _header_map) = self
+            .podman_service
+            .post_json_stream(&endpoint, Some(query), header, None)
+            .await?;
+        Ok(result_stream)
+    }
+
+    async fn modify<T: Serialize>(
+        &self,
+        manifest_name: &str,
+        tls_verify: Option<bool>,
+        header: Option<HashMap<String, String>>,
+        body: &ModifyManifestBody<'_, T>,
+    ) -> Result<ModifyManifestResponse> {
+        let endpoint = format!("/libpod/manifests/{}", manifest_name);
+        let query = tls_verify.map(|tls_verify| format!("tlsVerify={}", tls_verify));
+        let body = serde_json::to_string(body)?;
+        let service_response = self
+            .podman_service
+            .put_request(&endpoint, query, header, Some(body))
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+}
@@ -0,0 +1,298 @@
+#[cfg(any(test, feature = "examples"))]
+use crate::example_values_trait::ExampleValues;
+use crate::images::registry_auth::RegistryAuth;
+#[cfg(feature = "builder")]
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct CreateManifestParameter {
+    #[serde(skip_serializing)]
+    pub manifest_name: String,
+    /// Images or manifest lists to add to the new manifest list.
+    #[serde(skip_serializing)]
+    pub images: Option<Vec<String>>,
+    /// Add all images of manifest lists in `images` instead of the one matching the platform.
+    pub all: Option<bool>,
+    /// Modify the existing manifest list if `manifest_name` already exists.
+    pub amend: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for CreateManifestParameter {
+    fn example() -> Self {
+        Self {
+            manifest_name: String::from("localhost/create_manifest_parameter"),
+            images: Some(vec![String::from("testcontainer")]),
+            all: Some(false),
+            amend: Some(false),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct InspectManifestParameter {
+    #[serde(skip_serializing)]
+    pub manifest_name: String,
+    #[serde(rename = "tlsVerify")]
+    pub tls_verify: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for InspectManifestParameter {
+    fn example() -> Self {
+        Self {
+            manifest_name: String::from("localhost/inspect_manifest_parameter"),
+            tls_verify: Some(true),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ManifestExistsParameter {
+    pub manifest_name: String,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ManifestExistsParameter {
+    fn example() -> Self {
+        Self {
+            manifest_name: String::from("localhost/manifest_exists_parameter"),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct DeleteManifestParameter {
+    pub manifest_name: String,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for DeleteManifestParameter {
+    fn example() -> Self {
+        Self {
+            manifest_name: String::from("localhost/delete_manifest_parameter"),
+        }
+    }
+}
+
+//json
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct AddToManifestParameter {
+    #[serde(skip_serializing)]
+    pub manifest_name: String,
+    /// Sent as `X-Registry-Auth` header, used if `images` have to be looked up in a registry.
+    #[serde(skip_serializing)]
+    pub registry_auth: Option<RegistryAuth>,
+    /// Sent as query parameter.
+    #[serde(skip_serializing)]
+    pub tls_verify: Option<bool>,
+    /// Images or manifest lists to add, e.g. `docker://quay.io/podman/hello:latest` or the name
+    /// of a local image.
+    pub images: Vec<String>,
+    /// Add all images of manifest lists in `images` instead of the one matching the platform.
+    pub all: Option<bool>,
+    /// Annotations of the added instances.
+    pub annotations: Option<HashMap<String, String>>,
+    /// Overrides the architecture of the added instances.
+    pub arch: Option<String>,
+    pub features: Option<Vec<String>>,
+    /// Overrides the operating system of the added instances.
+    pub os: Option<String>,
+    pub os_features: Option<Vec<String>>,
+    pub os_version: Option<String>,
+    /// Overrides the architecture variant of the added instances, e.g. `v8`.
+    pub variant: Option<String>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for AddToManifestParameter {
+    fn example() -> Self {
+        let mut annotations = HashMap::new();
+        annotations.insert(String::from("podtender"), String::from("example"));
+        Self {
+            manifest_name: String::from("localhost/add_to_manifest_parameter"),
+            registry_auth: None,
+            tls_verify: Some(true),
+            images: vec![String::from("testcontainer")],
+            all: Some(false),
+            annotations: Some(annotations),
+            arch: Some(String::from("arm64")),
+            features: None,
+            os: Some(String::from("linux")),
+            os_features: None,
+            os_version: None,
+            variant: Some(String::from("v8")),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct RemoveFromManifestParameter {
+    pub manifest_name: String,
+    /// Digest of the instance to remove, see `ManifestDescriptor::digest`.
+    pub digest: String,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for RemoveFromManifestParameter {
+    fn example() -> Self {
+        Self {
+            manifest_name: String::from("localhost/remove_from_manifest_parameter"),
+            digest: String::from(
+                "sha256:0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+        }
+    }
+}
+
+//json
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct AnnotateManifestParameter {
+    #[serde(skip_serializing)]
+    pub manifest_name: String,
+    /// Digest of the instance to annotate, see `ManifestDescriptor::digest`.
+    #[serde(skip_serializing)]
+    pub digest: String,
+    pub annotations: Option<HashMap<String, String>>,
+    pub arch: Option<String>,
+    pub features: Option<Vec<String>>,
+    pub os: Option<String>,
+    pub os_features: Option<Vec<String>>,
+    pub os_version: Option<String>,
+    pub variant: Option<String>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for AnnotateManifestParameter {
+    fn example() -> Self {
+        let mut annotations = HashMap::new();
+        annotations.insert(String::from("podtender"), String::from("annotated"));
+        Self {
+            manifest_name: String::from("localhost/annotate_manifest_parameter"),
+            digest: String::from(
+                "sha256:0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            annotations: Some(annotations),
+            arch: Some(String::from("amd64")),
+            features: None,
+            os: Some(String::from("linux")),
+            os_features: None,
+            os_version: None,
+            variant: None,
+        }
+    }
+}
+
+/// Body of the manifest modify request, `operation` selects what `AddToManifestParameter`,
+/// `RemoveFromManifestParameter` and `AnnotateManifestParameter` do.
+#[derive(Serialize, Debug)]
+pub(crate) struct ModifyManifestBody<'a, T: Serialize> {
+    pub(crate) operation: &'static str,
+    #[serde(flatten)]
+    pub(crate) options: &'a T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) images: Option<Vec<&'a str>>,
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct PushManifestParameter {
+    #[serde(skip_serializing)]
+    pub manifest_name: String,
+    /// Where to push the manifest list to, e.g. `docker://registry.example.com/image:latest`.
+    #[serde(skip_serializing)]
+    pub destination: String,
+    /// Sent as `X-Registry-Auth` header.
+    #[serde(skip_serializing)]
+    pub registry_auth: Option<RegistryAuth>,
+    /// Push the images of all instances, not only the manifest list. Podman defaults to `true`.
+    pub all: Option<bool>,
+    /// `gzip`, `zstd` or `zstd:chunked`.
+    #[serde(rename = "compressionFormat")]
+    pub compression_format: Option<String>,
+    /// Only report errors and the manifest digest. Podman defaults to `true`.
+    pub quiet: Option<bool>,
+    #[serde(rename = "removeSignatures")]
+    pub remove_signatures: Option<bool>,
+    #[serde(rename = "tlsVerify")]
+    pub tls_verify: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for PushManifestParameter {
+    fn example() -> Self {
+        Self {
+            manifest_name: String::from("localhost/push_manifest_parameter"),
+            destination: String::from("oci:/tmp/push_manifest_parameter:latest"),
+            registry_auth: None,
+            all: Some(true),
+            compression_format: Some(String::from("gzip")),
+            quiet: Some(false),
+            remove_signatures: Some(false),
+            tls_verify: Some(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modify_body_contains_operation_and_options() {
+        let parameter = AnnotateManifestParameter {
+            manifest_name: String::from("list"),
+            digest: String::from("sha256:abc"),
+            arch: Some(String::from("arm64")),
+            ..Default::default()
+        };
+        let body = ModifyManifestBody {
+            operation: "annotate",
+            options: &parameter,
+            images: Some(vec![parameter.digest.as_str()]),
+        };
+        assert_eq!(
+            r#"{"operation":"annotate","arch":"arm64","images":["sha256:abc"]}"#,
+            serde_json::to_string(&body).unwrap()
+        );
+
+        let body = ModifyManifestBody {
+            operation: "remove",
+            options: &(),
+            images: Some(vec!["sha256:abc"]),
+        };
+        assert_eq!(
+            r#"{"operation":"remove","images":["sha256:abc"]}"#,
+            serde_json::to_string(&body).unwrap()
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CreateManifestResponse {
+    #[serde(rename = "Id")]
+    pub id: Option<String>,
+}
+
+/// A manifest list, either an OCI image index or a docker manifest list.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InspectManifestResponse {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: Option<i64>,
+    /// `application/vnd.oci.image.index.v1+json` or
+    /// `application/vnd.docker.distribution.manifest.list.v2+json`.
+    #[serde(rename = "mediaType")]
+    pub media_type: Option<String>,
+    /// One entry per instance, usually one per platform.
+    pub manifests: Option<Vec<ManifestDescriptor>>,
+    pub annotations: Option<HashMap<String, String>>,
+}
+
+/// Descriptor of an image manifest inside a manifest list.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestDescriptor {
+    #[serde(rename = "mediaType")]
+    pub media_type: Option<String>,
+    /// Digest of the image manifest, identifies the instance in `Manifests::remove` and
+    /// `Manifests::annotate`.
+    pub digest: Option<String>,
+    pub size: Option<i64>,
+    pub urls: Option<Vec<String>>,
+    pub annotations: Option<HashMap<String, String>>,
+    pub platform: Option<ManifestPlatform>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestPlatform {
+    pub architecture: Option<String>,
+    pub os: Option<String>,
+    #[serde(rename = "os.version")]
+    pub os_version: Option<String>,
+    #[serde(rename = "os.features")]
+    pub os_features: Option<Vec<String>>,
+    pub variant: Option<String>,
+    pub features: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ModifyManifestResponse {
+    /// Id of the manifest list.
+    #[serde(rename = "Id")]
+    pub id: Option<String>,
+    /// Added or removed images.
+    pub images: Option<Vec<String>>,
+    pub errors: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PushManifestResponse {
+    /// Digest of the pushed manifest list, set by the last message.
+    #[serde(rename = "Id")]
+    pub id: Option<String>,
+    pub error: Option<String>,
+    pub stream: Option<String>,
+}
@@ -4,7 +4,7 @@ use crate::networks::response_types::*;
 use crate::podman_service::PodmanService;
 use crate::utils;
 use std::convert::TryInto;
-#[cfg(feature = "enable-tracing")]
+#[cfg(feature = "tracing")]
 use tracing::instrument;
 
 /// Network operations.
@@ -19,13 +19,12 @@ impl<'service> Networks<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkDeleteLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Networks.remove"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Networks.remove"))]
     pub async fn remove(
         &self,
         parameter: RemoveNetworkParameter,
     ) -> Result<Vec<RemoveNetworkResponse>> {
-        let endpoint =
-            utils::create_endpoint(&format!("/libpod/networks/{}", parameter.network_name));
+        let endpoint = format!("/libpod/networks/{}", parameter.network_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -35,15 +34,9 @@ impl<'service> Networks<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkConnectLibpod>
-    #[cfg_attr(
-        feature = "enable-tracing",
-        instrument(name = "Networks.connect_container")
-    )]
+    #[cfg_attr(feature = "tracing", instrument(name = "Networks.connect_container"))]
     pub async fn connect_container(&self, parameter: ConnectContainerParameter) -> Result<()> {
-        let endpoint = utils::create_endpoint(&format!(
-            "/libpod/networks/{}/connect",
-            parameter.network_name
-        ));
+        let endpoint = format!("/libpod/networks/{}/connect", parameter.network_name);
         let body = serde_json::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -54,17 +47,14 @@ impl<'service> Networks<'service> {
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkDisconnectLibpod>
     #[cfg_attr(
-        feature = "enable-tracing",
+        feature = "tracing",
         instrument(name = "Networks.disconnect_container")
     )]
     pub async fn disconnect_container(
         &self,
         parameter: DisconnectContainerParameter,
     ) -> Result<()> {
-        let endpoint = utils::create_endpoint(&format!(
-            "/libpod/networks/{}/disconnect",
-            parameter.network_name
-        ));
+        let endpoint = format!("/libpod/networks/{}/disconnect", parameter.network_name);
         let body = serde_json::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -74,12 +64,9 @@ impl<'service> Networks<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkExistsLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Networks.exists"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Networks.exists"))]
     pub async fn exists(&self, parameter: NetworkExistsParameter) -> Result<()> {
-        let endpoint = utils::create_endpoint(&format!(
-            "/libpod/networks/{}/exists",
-            parameter.network_name
-        ));
+        let endpoint = format!("/libpod/networks/{}/exists", parameter.network_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -88,13 +75,12 @@ impl<'service> Networks<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkInspectLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Networks.inspect"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Networks.inspect"))]
     pub async fn inspect(
         &self,
         parameter: InspectNetworkParameter,
     ) -> Result<InspectNetworkResponse> {
-        let endpoint =
-            utils::create_endpoint(&format!("/libpod/networks/{}/json", parameter.network_name));
+        let endpoint = format!("/libpod/networks/{}/json", parameter.network_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -103,47 +89,47 @@ impl<'service> Networks<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkCreateLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Networks.create"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Networks.create"))]
     pub async fn create(&self, parameter: CreateNetworkParameter) -> Result<CreateNetworkResponse> {
-        let endpoint = utils::create_endpoint("/libpod/networks/create");
+        let endpoint = "/libpod/networks/create";
         let body = serde_json::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, None, None, Some(body))
+            .post_request(endpoint, None, None, Some(body))
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkListLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Networks.list"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Networks.list"))]
     pub async fn list(
         &self,
         parameter: ListNetworksParameter,
     ) -> Result<Vec<ListNetworksResponseEntry>> {
         let parameter: ListNetworksParameterQuery = parameter.try_into()?;
-        let endpoint = utils::create_endpoint("/libpod/networks/json");
+        let endpoint = "/libpod/networks/json";
 
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .get_request(&endpoint, Some(query), None, None)
+            .get_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/NetworkPruneLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Networks.prune"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Networks.prune"))]
     pub async fn prune(
         &self,
         parameter: PruneNetworksParameter,
     ) -> Result<Vec<PruneNetworksResponseEntry>> {
         let parameter: PruneNetworksParameterQuery = parameter.try_into()?;
-        let endpoint = utils::create_endpoint("/libpod/networks/prune");
+        let endpoint = "/libpod/networks/prune";
 
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, Some(query), None, None)
+            .post_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
@@ -0,0 +1,329 @@
+//! Version of the libpod API, negotiated with the podman service or pinned by the caller.
+
+use crate::error::{PodtenderError, Result};
+use crate::podman_service::PodmanService;
+use crate::PODMAN_API_VERSION;
+use hyper::{Body, Request, Uri};
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Version of the libpod API, e.g. `4.4.0`. The API version matches the podman version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        ApiVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Parses `4.4.0`, `v4.4` and versions with pre-release or build suffix like `5.0.0-dev`.
+impl FromStr for ApiVersion {
+    type Err = PodtenderError;
+
+    fn from_str(version: &str) -> Result<Self> {
+        let invalid_version = || PodtenderError::InvalidApiVersion(version.to_string());
+        let release = version
+            .strip_prefix('v')
+            .unwrap_or(version)
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default();
+        let mut parts = release.split('.').map(|part| part.parse::<u32>());
+        let major = parts.next().ok_or_else(invalid_version)?;
+        let minor = parts.next().ok_or_else(invalid_version)?;
+        let patch = parts.next().unwrap_or(Ok(0));
+        match (major, minor, patch, parts.next()) {
+            (Ok(major), Ok(minor), Ok(patch), None) => Ok(ApiVersion::new(major, minor, patch)),
+            _ => Err(invalid_version()),
+        }
+    }
+}
+
+/// The parts of the `/version` response naming the libpod API version.
+#[derive(Deserialize, Debug)]
+struct VersionResponse {
+    #[serde(rename = "Components", default)]
+    components: Vec<VersionComponent>,
+    /// Podman version, equal to the API version.
+    #[serde(rename = "Version")]
+    version: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct VersionComponent {
+    #[serde(rename = "Name")]
+    name: Option<String>,
+    #[serde(rename = "Details", default)]
+    details: VersionComponentDetails,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct VersionComponentDetails {
+    #[serde(rename = "APIVersion")]
+    api_version: Option<String>,
+}
+
+impl PodmanService {
+    /// Queries the API version of the service from the unversioned `/version` endpoint.
+    pub(crate) async fn negotiate_api_version(&self) -> Result<ApiVersion> {
+        let request = Request::get(Uri::from_static("http://d/version")).body(Body::empty())?;
+        let response = self.client.request(request).await?;
+        let status_code = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status_code.is_success() {
+            return Err(PodtenderError::InvalidApiVersion(format!(
+                "/version answered with {}: {}",
+                status_code,
+                String::from_utf8_lossy(&body)
+            )));
+        }
+
+        let version: VersionResponse = serde_json::from_slice(&body)?;
+        version
+            .components
+            .into_iter()
+            .find(|component| component.name.as_deref() == Some("Podman Engine"))
+            .and_then(|component| component.details.api_version)
+            .or(version.version)
+            .ok_or_else(|| PodtenderError::InvalidApiVersion(String::from("missing in /version")))?
+            .parse()
+    }
+
+    /// Path prefix of every request, the API version of the service but at most the version
+    /// podtender targets.
+    pub(crate) async fn api_version_prefix(&self) -> Result<String> {
+        let version = self.api_version().await?.min(PODMAN_API_VERSION);
+        Ok(format!("/v{}", version))
+    }
+
+    /// Fails with `PodtenderError::UnsupportedByServer` if the service's API version is older
+    /// than `required`.
+    pub(crate) async fn require_api_version(&self, required: ApiVersion) -> Result<()> {
+        let actual = self.api_version().await?;
+        if actual < required {
+            Err(PodtenderError::UnsupportedByServer { required, actual })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::containers::parameter_types::UpdateContainerParameter;
+    use crate::example_values_trait::ExampleValues;
+    use crate::secrets::parameter_types::InspectSecretParameter;
+    use crate::system::parameter_types::EventsParameter;
+    use crate::volumes::parameter_types::VolumeExistsParameter;
+    use hyper::server::conn::Http;
+    use hyper::service::service_fn;
+    use hyper::{Response, StatusCode};
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    /// Answers `/version` like podman `version` with `version_status` and everything else with
+    /// 204, records the paths.
+    async fn serve_podman(
+        version: &'static str,
+        version_status: StatusCode,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("tcp://{}", listener.local_addr().unwrap());
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        let recorded = paths.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                let service = service_fn(move |request: Request<Body>| {
+                    let path = request.uri().path().to_string();
+                    recorded.lock().unwrap().push(path.clone());
+                    async move {
+                        let response = if path == "/version" {
+                            let mut response = Response::new(Body::from(format!(
+                                r#"{{"Platform":{{"Name":"linux/amd64/fedora-38"}},"Components":[{{"Name":"Podman Engine","Version":"{version}","Details":{{"APIVersion":"{version}","Arch":"amd64","MinAPIVersion":"4.0.0"}}}}],"Version":"{version}","ApiVersion":"1.41","MinAPIVersion":"1.24"}}"#
+                            )));
+                            *response.status_mut() = version_status;
+                            response
+                        } else {
+                            let mut response = Response::new(Body::empty());
+                            *response.status_mut() = StatusCode::NO_CONTENT;
+                            response
+                        };
+                        Ok::<_, hyper::Error>(response)
+                    }
+                });
+                tokio::spawn(Http::new().serve_connection(stream, service));
+            }
+        });
+        (uri, paths)
+    }
+
+    #[test]
+    fn parse_api_versions() {
+        assert_eq!(ApiVersion::new(4, 4, 0), "4.4.0".parse().unwrap());
+        assert_eq!(ApiVersion::new(4, 4, 0), "v4.4".parse().unwrap());
+        assert_eq!(ApiVersion::new(5, 0, 0), "5.0.0-dev".parse().unwrap());
+        assert_eq!("4.4.0", ApiVersion::new(4, 4, 0).to_string());
+        assert!(ApiVersion::new(4, 10, 0) > ApiVersion::new(4, 4, 1));
+        for version in ["", "4", "4.x.0", "4.4.0.1"] {
+            assert!(matches!(
+                version.parse::<ApiVersion>(),
+                Err(PodtenderError::InvalidApiVersion(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn negotiate_once_and_use_older_server_version() {
+        let (uri, paths) = serve_podman("4.3.1", StatusCode::OK).await;
+        let podman_service = PodmanService::from_uri(&uri).unwrap();
+        let parameter = VolumeExistsParameter {
+            volume_name: String::from("test"),
+        };
+
+        podman_service
+            .volumes()
+            .exists(parameter.clone())
+            .await
+            .unwrap();
+        podman_service.volumes().exists(parameter).await.unwrap();
+
+        assert_eq!(
+            ApiVersion::new(4, 3, 1),
+            podman_service.api_version().await.unwrap()
+        );
+        assert_eq!(
+            vec![
+                "/version",
+                "/v4.3.1/libpod/volumes/test/exists",
+                "/v4.3.1/libpod/volumes/test/exists",
+            ],
+            *paths.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn pinned_version_skips_negotiation() {
+        let (uri, paths) = serve_podman("4.3.1", StatusCode::OK).await;
+        let podman_service = PodmanService::from_uri(&uri)
+            .unwrap()
+            .with_api_version(ApiVersion::new(9, 0, 0));
+
+        podman_service
+            .volumes()
+            .exists(VolumeExistsParameter {
+                volume_name: String::from("test"),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![format!(
+                "/v{}/libpod/volumes/test/exists",
+                PODMAN_API_VERSION
+            )],
+            *paths.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_by_server() {
+        let (uri, paths) = serve_podman("4.3.1", StatusCode::OK).await;
+        let podman_service = PodmanService::from_uri(&uri).unwrap();
+
+        let podtender_result = podman_service
+            .secrets()
+            .inspect(InspectSecretParameter::example())
+            .await;
+
+        match podtender_result {
+            Err(PodtenderError::UnsupportedByServer { required, actual }) => {
+                assert_eq!(ApiVersion::new(4, 5, 0), required);
+                assert_eq!(ApiVersion::new(4, 3, 1), actual);
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(vec!["/version"], *paths.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn update_restart_policy_unsupported_by_server() {
+        let (uri, paths) = serve_podman("4.3.1", StatusCode::OK).await;
+        let podman_service = PodmanService::from_uri(&uri).unwrap();
+
+        let podtender_result = podman_service
+            .containers()
+            .update(UpdateContainerParameter {
+                restart_policy: Some(String::from("always")),
+                ..UpdateContainerParameter::example()
+            })
+            .await;
+
+        assert!(matches!(
+            podtender_result,
+            Err(PodtenderError::UnsupportedByServer { .. })
+        ));
+        assert_eq!(vec!["/version"], *paths.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn show_secret_with_podman_4_5() {
+        let (uri, paths) = serve_podman("4.5.1", StatusCode::OK).await;
+        let podman_service = PodmanService::from_uri(&uri).unwrap();
+
+        let podtender_result = podman_service
+            .secrets()
+            .inspect(InspectSecretParameter::example())
+            .await;
+
+        // The fake service answers with an empty body, only the guard and the path matter here.
+        assert!(!matches!(
+            podtender_result,
+            Err(PodtenderError::UnsupportedByServer { .. })
+        ));
+        assert_eq!(
+            vec![
+                String::from("/version"),
+                format!(
+                    "/v4.5.0/libpod/secrets/{}/json",
+                    InspectSecretParameter::example().secret_name
+                ),
+            ],
+            *paths.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_negotiation_in_stream() {
+        let (uri, paths) = serve_podman("4.3.1", StatusCode::INTERNAL_SERVER_ERROR).await;
+        let podman_service = PodmanService::from_uri(&uri).unwrap();
+
+        let podtender_result = podman_service
+            .system()
+            .get_events_streaming(EventsParameter::example())
+            .await;
+
+        assert!(matches!(
+            podtender_result,
+            Err(PodtenderError::InvalidApiVersion(_))
+        ));
+        assert_eq!(vec!["/version"], *paths.lock().unwrap());
+    }
+}
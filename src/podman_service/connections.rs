@@ -0,0 +1,381 @@
+//! Resolves the podman service like `podman --remote` does: `CONTAINER_HOST`,
+//! `CONTAINER_CONNECTION`, the connections of `podman system connection` (stored in
+//! `podman-connections.json` and `containers.conf`) and finally the local podman socket.
+
+use crate::error::{PodtenderError, Result};
+use crate::podman_service::transport::{SshConfig, Transport};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A connection added with `podman system connection add`.
+#[derive(Deserialize, Debug, Clone)]
+struct Destination {
+    #[serde(alias = "URI")]
+    uri: String,
+    #[serde(alias = "Identity")]
+    identity: Option<PathBuf>,
+}
+
+/// The parts of `containers.conf` describing connections.
+#[derive(Deserialize, Debug, Default)]
+struct ContainersConf {
+    #[serde(default)]
+    engine: EngineTable,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct EngineTable {
+    active_service: Option<String>,
+    #[serde(default)]
+    service_destinations: HashMap<String, Destination>,
+}
+
+/// Content of `podman-connections.json`, used by podman 5 instead of `containers.conf`.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "PascalCase")]
+struct ConnectionsFile {
+    #[serde(default)]
+    connection: ConnectionsTable,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "PascalCase")]
+struct ConnectionsTable {
+    default: Option<String>,
+    #[serde(default)]
+    connections: HashMap<String, Destination>,
+}
+
+/// Where the podman service is looked up. Filled from the process environment by `from_env`.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ConnectionSources {
+    /// `CONTAINER_HOST`
+    pub(crate) container_host: Option<String>,
+    /// `CONTAINER_SSHKEY`, identity used for `container_host`.
+    pub(crate) container_ssh_key: Option<PathBuf>,
+    /// `CONTAINER_CONNECTION`
+    pub(crate) container_connection: Option<String>,
+    /// `podman-connections.json` files, highest priority first.
+    pub(crate) connections_files: Vec<PathBuf>,
+    /// `containers.conf` files, highest priority first.
+    pub(crate) containers_conf_files: Vec<PathBuf>,
+    /// Local podman sockets, the first existing one is used.
+    pub(crate) sockets: Vec<PathBuf>,
+}
+
+impl ConnectionSources {
+    /// The sources podman uses: user configuration in `$XDG_CONFIG_HOME` (or `~/.config`)
+    /// before `/etc` and `/usr/share`, `$CONTAINERS_CONF` replaces all `containers.conf` files.
+    /// The rootless socket `$XDG_RUNTIME_DIR/podman/podman.sock` is preferred over the rootful
+    /// `/run/podman/podman.sock`.
+    pub(crate) fn from_env() -> Self {
+        let env = |key: &str| std::env::var_os(key).filter(|value| !value.is_empty());
+        let config_home = env("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+        let mut connections_files = vec![];
+        let mut containers_conf_files = vec![];
+        if let Some(config_home) = &config_home {
+            connections_files.push(config_home.join("containers/podman-connections.json"));
+        }
+        connections_files.push(PathBuf::from("/etc/containers/podman-connections.json"));
+        match env("CONTAINERS_CONF") {
+            Some(path) => containers_conf_files.push(PathBuf::from(path)),
+            None => {
+                if let Some(config_home) = &config_home {
+                    containers_conf_files.push(config_home.join("containers/containers.conf"));
+                }
+                containers_conf_files.push(PathBuf::from("/etc/containers/containers.conf"));
+                containers_conf_files.push(PathBuf::from("/usr/share/containers/containers.conf"));
+            }
+        }
+
+        let mut sockets = vec![];
+        if let Some(runtime_dir) = env("XDG_RUNTIME_DIR") {
+            sockets.push(PathBuf::from(runtime_dir).join("podman/podman.sock"));
+        }
+        sockets.push(PathBuf::from("/run/podman/podman.sock"));
+
+        ConnectionSources {
+            container_host: env("CONTAINER_HOST").and_then(|host| host.into_string().ok()),
+            container_ssh_key: env("CONTAINER_SSHKEY").map(PathBuf::from),
+            container_connection: env("CONTAINER_CONNECTION")
+                .and_then(|name| name.into_string().ok()),
+            connections_files,
+            containers_conf_files,
+            sockets,
+        }
+    }
+
+    /// Resolves the service selected by the environment, falls back to the default connection
+    /// and the local sockets.
+    pub(crate) fn resolve(&self) -> Result<Transport> {
+        let mut tried = vec![];
+        match &self.container_host {
+            Some(uri) => return destination_transport(uri, self.container_ssh_key.as_ref()),
+            None => tried.push(String::from("CONTAINER_HOST (not set)")),
+        }
+        match &self.container_connection {
+            Some(name) => return self.resolve_connection(name),
+            None => tried.push(String::from("CONTAINER_CONNECTION (not set)")),
+        }
+
+        let connections_files = self.read_connections_files()?;
+        let containers_confs = self.read_containers_conf_files()?;
+        let default = connections_files
+            .iter()
+            .find_map(|(_, file)| file.connection.default.as_ref())
+            .or_else(|| {
+                containers_confs
+                    .iter()
+                    .find_map(|(_, conf)| conf.engine.active_service.as_ref())
+            });
+        if let Some(name) = default {
+            return self.resolve_connection(name);
+        }
+        for path in self
+            .connections_files
+            .iter()
+            .chain(&self.containers_conf_files)
+        {
+            if connections_files.iter().any(|(read, _)| read == path)
+                || containers_confs.iter().any(|(read, _)| read == path)
+            {
+                tried.push(format!(
+                    "default connection in {} (not set)",
+                    path.display()
+                ));
+            } else {
+                tried.push(format!(
+                    "default connection in {} (no such file)",
+                    path.display()
+                ));
+            }
+        }
+
+        for socket in &self.sockets {
+            if socket.exists() {
+                return Ok(Transport::Unix(socket.clone()));
+            }
+            tried.push(format!("socket {} (no such file)", socket.display()));
+        }
+        Err(PodtenderError::ServiceNotFound(tried))
+    }
+
+    /// Resolves the connection `name` of `podman system connection`.
+    pub(crate) fn resolve_connection(&self, name: &str) -> Result<Transport> {
+        let connections = self
+            .read_connections_files()?
+            .into_iter()
+            .map(|(path, mut file)| (path, file.connection.connections.remove(name)));
+        let service_destinations = self
+            .read_containers_conf_files()?
+            .into_iter()
+            .map(|(path, mut conf)| (path, conf.engine.service_destinations.remove(name)));
+
+        let mut tried = vec![];
+        for (path, destination) in connections.chain(service_destinations) {
+            match destination {
+                Some(destination) => {
+                    return destination_transport(&destination.uri, destination.identity.as_ref())
+                }
+                None => tried.push(format!("connection `{}` in {}", name, path.display())),
+            }
+        }
+        if tried.is_empty() {
+            tried.push(format!(
+                "connection `{}` (no podman-connections.json or containers.conf found)",
+                name
+            ));
+        }
+        Err(PodtenderError::ServiceNotFound(tried))
+    }
+
+    /// The existing `podman-connections.json` files with their path.
+    fn read_connections_files(&self) -> Result<Vec<(PathBuf, ConnectionsFile)>> {
+        let mut files = vec![];
+        for path in &self.connections_files {
+            if let Some(content) = read_if_exists(path)? {
+                let file =
+                    serde_json::from_str(&content).map_err(|error| invalid_config(path, error))?;
+                files.push((path.clone(), file));
+            }
+        }
+        Ok(files)
+    }
+
+    /// The existing `containers.conf` files with their path.
+    fn read_containers_conf_files(&self) -> Result<Vec<(PathBuf, ContainersConf)>> {
+        let mut confs = vec![];
+        for path in &self.containers_conf_files {
+            if let Some(content) = read_if_exists(path)? {
+                let conf = toml::from_str(&content).map_err(|error| invalid_config(path, error))?;
+                confs.push((path.clone(), conf));
+            }
+        }
+        Ok(confs)
+    }
+}
+
+fn destination_transport(uri: &str, identity: Option<&PathBuf>) -> Result<Transport> {
+    if uri.starts_with("ssh://") {
+        let ssh_config = SshConfig {
+            // podman stores connections without identity with an empty one
+            identity: identity
+                .filter(|identity| !identity.as_os_str().is_empty())
+                .cloned(),
+            ..Default::default()
+        };
+        Transport::from_uri_with_ssh(uri, &ssh_config)
+    } else {
+        Transport::from_uri(uri)
+    }
+}
+
+fn read_if_exists(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn invalid_config(path: &Path, error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid {}: {}", path.display(), error),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sources in a fresh temporary directory, without any file.
+    fn temp_sources(name: &str) -> (PathBuf, ConnectionSources) {
+        let dir = std::env::temp_dir().join(format!("podtender-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let sources = ConnectionSources {
+            connections_files: vec![dir.join("podman-connections.json")],
+            containers_conf_files: vec![dir.join("containers.conf")],
+            sockets: vec![dir.join("rootless.sock"), dir.join("rootful.sock")],
+            ..Default::default()
+        };
+        (dir, sources)
+    }
+
+    #[test]
+    fn container_host_takes_precedence() {
+        let (dir, mut sources) = temp_sources("container-host");
+        sources.container_host = Some(String::from("ssh://core@remote/run/podman/podman.sock"));
+        sources.container_ssh_key = Some(PathBuf::from("/home/core/.ssh/id_ed25519"));
+        sources.container_connection = Some(String::from("missing"));
+
+        let transport = sources.resolve();
+        let _ = std::fs::remove_dir_all(&dir);
+        match transport {
+            Ok(Transport::Ssh {
+                destination,
+                config,
+                ..
+            }) => {
+                assert_eq!("core@remote", destination);
+                assert_eq!(sources.container_ssh_key, config.identity);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn connections_json_before_containers_conf() {
+        let (dir, mut sources) = temp_sources("connections-json");
+        std::fs::write(
+            dir.join("podman-connections.json"),
+            r#"{"Connection":{"Default":"json","Connections":{"json":{"URI":"tcp://localhost:8080","Identity":"","IsMachine":false}}},"Farm":{}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("containers.conf"),
+            r#"
+[engine]
+active_service = "conf"
+
+[engine.service_destinations.conf]
+uri = "unix:///run/conf.sock"
+
+[engine.service_destinations.remote]
+uri = "ssh://root@remote:2222/run/podman/podman.sock"
+identity = "/root/.ssh/id_rsa"
+"#,
+        )
+        .unwrap();
+
+        let default = sources.resolve();
+        sources.container_connection = Some(String::from("remote"));
+        let remote = sources.resolve();
+        let missing = sources.resolve_connection("missing");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(matches!(default, Ok(Transport::Tcp(address)) if address == "localhost:8080"));
+        match remote {
+            Ok(Transport::Ssh {
+                destination,
+                port,
+                config,
+                ..
+            }) => {
+                assert_eq!("root@remote", destination);
+                assert_eq!(Some(2222), port);
+                assert_eq!(Some(PathBuf::from("/root/.ssh/id_rsa")), config.identity);
+            }
+            other => panic!("{:?}", other),
+        }
+        match missing {
+            Err(PodtenderError::ServiceNotFound(tried)) => assert_eq!(2, tried.len()),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn falls_back_to_sockets() {
+        let (dir, sources) = temp_sources("sockets");
+        let not_found = sources.resolve();
+        std::fs::write(dir.join("rootful.sock"), "").unwrap();
+        let rootful = sources.resolve();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        match not_found {
+            Err(PodtenderError::ServiceNotFound(tried)) => {
+                assert_eq!(
+                    vec![
+                        String::from("CONTAINER_HOST (not set)"),
+                        String::from("CONTAINER_CONNECTION (not set)"),
+                        format!(
+                            "default connection in {} (no such file)",
+                            dir.join("podman-connections.json").display()
+                        ),
+                        format!(
+                            "default connection in {} (no such file)",
+                            dir.join("containers.conf").display()
+                        ),
+                        format!(
+                            "socket {} (no such file)",
+                            dir.join("rootless.sock").display()
+                        ),
+                        format!(
+                            "socket {} (no such file)",
+                            dir.join("rootful.sock").display()
+                        ),
+                    ],
+                    tried
+                );
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(rootful, Ok(Transport::Unix(path)) if path == dir.join("rootful.sock")));
+    }
+}
@@ -0,0 +1,248 @@
+//! `podman system service` started and supervised by podtender, see
+//! `PodmanService::spawn_local`.
+
+use crate::error::{PodtenderError, Result};
+use crate::podman_service::transport::Transport;
+use crate::podman_service::PodmanService;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+
+/// Options for `PodmanService::spawn_local_with_config`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalServiceConfig {
+    /// The podman executable, defaults to `podman` from `PATH`.
+    pub podman_program: Option<PathBuf>,
+    /// Passed as `--time`, the service exits after being idle for this long. `0` never exits.
+    pub ttl: Duration,
+    /// How long to wait for the service to answer `/_ping`.
+    pub startup_timeout: Duration,
+}
+
+impl Default for LocalServiceConfig {
+    fn default() -> Self {
+        LocalServiceConfig {
+            podman_program: None,
+            ttl: Duration::from_secs(300),
+            startup_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// A `podman system service` listening on a socket in a temporary directory. Dereferences to the
+/// `PodmanService` connected to it. The service is killed and the directory removed on drop.
+#[derive(Debug)]
+pub struct LocalPodmanService {
+    podman_service: PodmanService,
+    child: Child,
+    dir: PathBuf,
+}
+
+impl LocalPodmanService {
+    pub(crate) async fn spawn(config: &LocalServiceConfig) -> Result<Self> {
+        static SERVICES: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "podtender-service-{}-{}",
+            std::process::id(),
+            SERVICES.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir)?;
+        let socket_path = dir.join("podman.sock");
+        // stderr goes to a file, a pipe nobody reads would block the service once it's full
+        let stderr = std::fs::File::create(dir.join("stderr.log"))?;
+
+        let child = Command::new(
+            config
+                .podman_program
+                .as_deref()
+                .unwrap_or("podman".as_ref()),
+        )
+        .arg("system")
+        .arg("service")
+        .arg(format!("--time={}", config.ttl.as_secs()))
+        .arg(format!("unix://{}", socket_path.display()))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(stderr)
+        .kill_on_drop(true)
+        .spawn();
+        let child = match child {
+            Ok(child) => child,
+            Err(error) => {
+                let _ = std::fs::remove_dir_all(&dir);
+                return Err(error.into());
+            }
+        };
+
+        // dropping it on error kills the service and removes the directory
+        let mut local_service = LocalPodmanService {
+            podman_service: PodmanService::with_transport(Transport::Unix(socket_path)),
+            child,
+            dir,
+        };
+        local_service
+            .wait_until_ready(config.startup_timeout)
+            .await?;
+        Ok(local_service)
+    }
+
+    /// Polls `/_ping` until the service answers, fails if it exits or doesn't answer in time.
+    async fn wait_until_ready(&mut self, timeout: Duration) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Err(self.start_failed(format!("exited with {}", status_code(status))));
+            }
+            if self.podman_service.check_socket_exists()
+                && self.podman_service.system().ping().await.is_ok()
+            {
+                return Ok(());
+            }
+            if started.elapsed() >= timeout {
+                return Err(self.start_failed(format!("not ready after {:?}", timeout)));
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    fn start_failed(&self, reason: String) -> PodtenderError {
+        let stderr = std::fs::read_to_string(self.dir.join("stderr.log")).unwrap_or_default();
+        PodtenderError::LocalServiceStartFailed { reason, stderr }
+    }
+
+    /// Path of the socket the service listens on.
+    pub fn socket_path(&self) -> PathBuf {
+        self.dir.join("podman.sock")
+    }
+}
+
+fn status_code(status: ExitStatus) -> String {
+    match status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => status.to_string(),
+    }
+}
+
+impl Deref for LocalPodmanService {
+    type Target = PodmanService;
+
+    fn deref(&self) -> &Self::Target {
+        &self.podman_service
+    }
+}
+
+impl Drop for LocalPodmanService {
+    /// The service itself is killed when `child` is dropped.
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    /// Stand-in for `podman system service`, answers `/version` like podman 4.4.0 and every other
+    /// request on the socket with 200.
+    const FAKE_PODMAN: &str = r#"#!/usr/bin/env python3
+import http.server, socketserver, sys
+class Handler(http.server.BaseHTTPRequestHandler):
+    def do_GET(self):
+        body = b'{"Version":"4.4.0"}' if self.path == "/version" else b"OK"
+        self.send_response(200)
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+    def log_message(self, *args):
+        pass
+assert sys.argv[1:3] == ["system", "service"] and sys.argv[3] == "--time=60"
+server = socketserver.UnixStreamServer(sys.argv[4][len("unix://"):], Handler)
+print("serving", file=sys.stderr, flush=True)
+server.serve_forever()
+"#;
+
+    const FAILING_PODMAN: &str = "#!/bin/sh\necho 'Error: unable to create socket' >&2\nexit 125\n";
+
+    /// Writes an executable stand-in. The file is synced and closed before returning, an open
+    /// write handle inherited by a process forked meanwhile makes exec fail with `ETXTBSY`.
+    fn write_program(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("podtender-{}-{}", name, std::process::id()));
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o755)
+            .open(&path)
+            .unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file.sync_all().unwrap();
+        drop(file);
+        path
+    }
+
+    #[tokio::test]
+    async fn spawn_and_kill_on_drop() {
+        if std::process::Command::new("python3")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            eprintln!(
+                "skipping spawn_and_kill_on_drop, python3 is required for the podman stand-in"
+            );
+            return;
+        }
+        let podman_program = write_program("fake-podman", FAKE_PODMAN);
+        let config = LocalServiceConfig {
+            podman_program: Some(podman_program.clone()),
+            ttl: Duration::from_secs(60),
+            ..Default::default()
+        };
+
+        let local_service = PodmanService::spawn_local_with_config(&config).await;
+        let _ = std::fs::remove_file(&podman_program);
+        let local_service = local_service.unwrap();
+        assert!(local_service.system().ping().await.is_ok());
+
+        let socket_path = local_service.socket_path();
+        let pid = local_service.child.id().unwrap();
+        drop(local_service);
+        assert!(!socket_path.exists());
+        // the killed process is gone once it's reaped, until then it's a zombie
+        let started = Instant::now();
+        while let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            if stat.contains(") Z") {
+                break;
+            }
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "service still running"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn surface_stderr_if_startup_fails() {
+        let podman_program = write_program("failing-podman", FAILING_PODMAN);
+        let config = LocalServiceConfig {
+            podman_program: Some(podman_program.clone()),
+            ..Default::default()
+        };
+
+        let local_service = PodmanService::spawn_local_with_config(&config).await;
+        let _ = std::fs::remove_file(&podman_program);
+        match local_service {
+            Err(PodtenderError::LocalServiceStartFailed { reason, stderr }) => {
+                assert_eq!("exited with exit code 125", reason);
+                assert_eq!("Error: unable to create socket\n", stderr);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+}
@@ -1,4 +1,13 @@
+mod api_version;
+mod connections;
+mod local_service;
 pub(crate) mod network_internals;
 mod podman_service;
+pub(crate) mod transport;
 
+pub use api_version::ApiVersion;
+pub use local_service::{LocalPodmanService, LocalServiceConfig};
 pub use podman_service::*;
+pub use transport::SshConfig;
+#[cfg(feature = "tls")]
+pub use transport::TlsConfig;
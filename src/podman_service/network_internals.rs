@@ -1,16 +1,19 @@
+use crate::containers::response_types::{LogFrame, LogStream};
 use crate::error::{PodtenderError, Result};
 use crate::podman_service::podman_service::PodmanService;
 use crate::utils;
-use asynchronous_codec::{FramedRead, JsonCodec, LinesCodec};
-use futures::{Stream, TryStreamExt};
+use asynchronous_codec::{BytesCodec, BytesMut, Decoder, FramedRead, JsonCodec};
+use futures::future::Either;
+use futures::{AsyncRead, Stream, TryStreamExt};
 use hyper::body::Bytes;
+use hyper::header::{CONNECTION, UPGRADE};
+use hyper::upgrade::Upgraded;
 use hyper::{Body, Method, Request, StatusCode};
-use hyper::{HeaderMap, Uri as HyperUri};
-use hyperlocal::Uri;
+use hyper::{HeaderMap, Uri};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::collections::HashMap;
-#[cfg(feature = "enable-tracing")]
+#[cfg(feature = "tracing")]
 use tracing::debug;
 
 #[derive(Debug)]
@@ -50,6 +53,16 @@ impl PodmanServiceResponseBytes {
 }
 
 impl PodmanService {
+    /// The uri of `endpoint` prefixed with the api version, the host is ignored by the connector
+    /// which always connects to the configured transport.
+    async fn request_uri(&self, endpoint: &str) -> Result<Uri> {
+        Ok(
+            format!("http://d{}{}", self.api_version_prefix().await?, endpoint)
+                .parse::<Uri>()
+                .map_err(hyper::http::Error::from)?,
+        )
+    }
+
     /// Send a get request to the podman api.
     pub(crate) async fn get_request(
         &self,
@@ -87,7 +100,6 @@ impl PodmanService {
     }
 
     /// Send a put request to the podman api.
-    #[allow(dead_code)]
     pub(crate) async fn put_request(
         &self,
         endpoint: &str,
@@ -137,9 +149,7 @@ impl PodmanService {
         let (status_code, stream, header_map) = self
             .receive_bytes_stream(Method::GET, endpoint, query, header, body)
             .await?;
-        let stream =
-            Box::pin(stream.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)))
-                .into_async_read();
+        let stream = Box::pin(stream.map_err(std::io::Error::other)).into_async_read();
 
         let codec = JsonCodec::<T, T>::new();
         Ok((
@@ -163,9 +173,7 @@ impl PodmanService {
         let (status_code, stream, header_map) = self
             .receive_bytes_stream(Method::POST, endpoint, query, header, body)
             .await?;
-        let stream =
-            Box::pin(stream.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)))
-                .into_async_read();
+        let stream = Box::pin(stream.map_err(std::io::Error::other)).into_async_read();
 
         let codec = JsonCodec::<T, T>::new();
         Ok((
@@ -175,27 +183,101 @@ impl PodmanService {
         ))
     }
 
-    /// Sends a get request to the podman api and returns a stream of lines.
-    pub(crate) async fn get_lines_stream(
+    /// Sends a get request to the podman api and returns a stream of log frames.
+    /// `timestamps` has to match the `timestamps` query parameter of the request.
+    pub(crate) async fn get_log_frames_stream(
         &self,
         endpoint: &str,
         query: Option<String>,
         header: Option<HashMap<String, String>>,
         body: Option<String>,
-    ) -> Result<(StatusCode, impl Stream<Item = Result<String>>, HeaderMap)> {
+        timestamps: bool,
+    ) -> Result<(StatusCode, impl Stream<Item = Result<LogFrame>>, HeaderMap)> {
         let (status_code, stream, header_map) = self
             .receive_bytes_stream(Method::GET, endpoint, query, header, body)
             .await?;
-        let stream =
-            Box::pin(stream.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)))
-                .into_async_read();
+        let stream = Box::pin(stream.map_err(std::io::Error::other)).into_async_read();
         Ok((
             status_code,
-            FramedRead::new(stream, LinesCodec).map_err(|e| e.into()),
+            FramedRead::new(stream, LogFrameCodec { timestamps }).map_err(|e| e.into()),
             header_map,
         ))
     }
 
+    /// Sends a post request to the podman api and returns a stream of demultiplexed stdout/stderr
+    /// frames, see [`demultiplex`].
+    pub(crate) async fn post_multiplexed_stream(
+        &self,
+        endpoint: &str,
+        query: Option<String>,
+        header: Option<HashMap<String, String>>,
+        body: Option<String>,
+        tty: bool,
+    ) -> Result<(
+        StatusCode,
+        impl Stream<Item = Result<StreamFrame>>,
+        HeaderMap,
+    )> {
+        let (status_code, stream, header_map) = self
+            .receive_bytes_stream(Method::POST, endpoint, query, header, body)
+            .await?;
+        let stream = Box::pin(stream.map_err(std::io::Error::other)).into_async_read();
+        Ok((status_code, demultiplex(stream, tty), header_map))
+    }
+
+    /// Sends a post request asking podman to upgrade the connection. Returns the upgraded
+    /// connection, used to attach to containers.
+    pub(crate) async fn post_upgrade_request(
+        &self,
+        endpoint: &str,
+        query: Option<String>,
+        header: Option<HashMap<String, String>>,
+        body: Option<String>,
+    ) -> Result<Upgraded> {
+        let endpoint = if let Some(query) = query {
+            format!("{}?{}", endpoint, query)
+        } else {
+            String::from(endpoint)
+        };
+
+        let mut request_builder = Request::builder()
+            .method(Method::POST)
+            .uri(self.request_uri(&endpoint).await?)
+            .header(CONNECTION, "Upgrade")
+            .header(UPGRADE, "tcp");
+        if let Some(header) = header {
+            for (key, value) in header {
+                request_builder = request_builder.header(key.as_str(), value.as_str());
+            }
+        };
+        let request = if let Some(body) = body {
+            request_builder = request_builder.header("content-type", "application/json");
+            request_builder.body(Body::from(body))?
+        } else {
+            request_builder.body(Body::empty())?
+        };
+
+        #[cfg(feature = "tracing")]
+        debug!(?request);
+
+        let response = self.client.request(request).await?;
+
+        #[cfg(feature = "tracing")]
+        debug!(?response);
+
+        if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+            Ok(hyper::upgrade::on(response).await?)
+        } else {
+            let (parts, body) = response.into_parts();
+            let response = PodmanServiceResponseBytes::new(
+                parts.status,
+                hyper::body::to_bytes(body).await?,
+                parts.headers,
+            );
+            Err(utils::handle_service_response_error(response))
+        }
+    }
+
     /// Sends a get request to the podman api and returns a stream of file chunks.
     /// Used to download exports, etc.
     pub(crate) async fn get_receive_file_chunks_stream(
@@ -240,7 +322,44 @@ impl PodmanService {
         E: Into<PodtenderError> + Send + 'static,
     {
         let response = self
-            .send_file_request(Method::POST, endpoint, query, header, body)
+            .send_file_request(
+                Method::POST,
+                endpoint,
+                query,
+                header,
+                body,
+                "application/x-tar",
+            )
+            .await?;
+        Ok(PodmanServiceResponseBytes::new(
+            response.status_code,
+            hyper::body::to_bytes(response.body).await?,
+            response.headers,
+        ))
+    }
+
+    /// Upload raw data, e.g. a secret, via post request as `application/octet-stream`.
+    pub(crate) async fn post_send_data_chunks_stream<S, O, E>(
+        &self,
+        endpoint: &str,
+        query: Option<String>,
+        header: Option<HashMap<String, String>>,
+        body: Option<S>,
+    ) -> Result<PodmanServiceResponseBytes>
+    where
+        S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<PodtenderError> + Send + 'static,
+    {
+        let response = self
+            .send_file_request(
+                Method::POST,
+                endpoint,
+                query,
+                header,
+                body,
+                "application/octet-stream",
+            )
             .await?;
         Ok(PodmanServiceResponseBytes::new(
             response.status_code,
@@ -249,6 +368,78 @@ impl PodmanService {
         ))
     }
 
+    /// Upload a file via put request.
+    pub(crate) async fn put_send_file_chunks_stream<S, O, E>(
+        &self,
+        endpoint: &str,
+        query: Option<String>,
+        header: Option<HashMap<String, String>>,
+        body: Option<S>,
+    ) -> Result<PodmanServiceResponseBytes>
+    where
+        S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<PodtenderError> + Send + 'static,
+    {
+        let response = self
+            .send_file_request(
+                Method::PUT,
+                endpoint,
+                query,
+                header,
+                body,
+                "application/x-tar",
+            )
+            .await?;
+        Ok(PodmanServiceResponseBytes::new(
+            response.status_code,
+            hyper::body::to_bytes(response.body).await?,
+            response.headers,
+        ))
+    }
+
+    /// Uploads a file via post request and returns a stream of results.
+    pub(crate) async fn post_send_file_json_stream<S, O, E, T>(
+        &self,
+        endpoint: &str,
+        query: Option<String>,
+        header: Option<HashMap<String, String>>,
+        body: Option<S>,
+    ) -> Result<(StatusCode, impl Stream<Item = Result<T>>, HeaderMap)>
+    where
+        S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<PodtenderError> + Send + 'static,
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        let response = self
+            .send_file_request(
+                Method::POST,
+                endpoint,
+                query,
+                header,
+                body,
+                "application/x-tar",
+            )
+            .await?;
+        if !response.status_code.is_success() {
+            let response = PodmanServiceResponseBytes::new(
+                response.status_code,
+                hyper::body::to_bytes(response.body).await?,
+                response.headers,
+            );
+            return Err(utils::handle_service_response_error(response));
+        }
+
+        let stream = Box::pin(response.body.map_err(std::io::Error::other)).into_async_read();
+        let codec = JsonCodec::<T, T>::new();
+        Ok((
+            response.status_code,
+            FramedRead::new(stream, codec).map_err(|e| e.into()),
+            response.headers,
+        ))
+    }
+
     /// Internal method to receive a byte stream after a request.
     async fn receive_bytes_stream(
         &self,
@@ -260,8 +451,7 @@ impl PodmanService {
     ) -> Result<(StatusCode, impl Stream<Item = Result<Bytes>>, HeaderMap)> {
         let response = self
             .send_json_request(method, endpoint, query, header, body)
-            .await
-            .unwrap();
+            .await?;
 
         if response.status_code.is_success() {
             Ok((
@@ -297,10 +487,7 @@ impl PodmanService {
 
         let mut request_builder = Request::builder()
             .method(method)
-            .uri(Into::<HyperUri>::into(Uri::new(
-                &self.path,
-                endpoint.as_str(),
-            )));
+            .uri(self.request_uri(&endpoint).await?);
         if let Some(header) = header {
             for (key, value) in header {
                 request_builder = request_builder.header(key.as_str(), value.as_str());
@@ -313,12 +500,12 @@ impl PodmanService {
             request_builder.body(Body::empty())?
         };
 
-        #[cfg(feature = "enable-tracing")]
+        #[cfg(feature = "tracing")]
         debug!(?request);
 
         let response = self.client.request(request).await?;
 
-        #[cfg(feature = "enable-tracing")]
+        #[cfg(feature = "tracing")]
         debug!(?response);
 
         let (parts, body) = response.into_parts();
@@ -329,7 +516,7 @@ impl PodmanService {
         ))
     }
 
-    /// Upload a file in the body as `application/x-tar`.
+    /// Upload a file in the body as `content_type`, e.g. `application/x-tar`.
     async fn send_file_request<S, O, E>(
         &self,
         method: Method,
@@ -337,6 +524,7 @@ impl PodmanService {
         query: Option<String>,
         header: Option<HashMap<String, String>>,
         body: Option<S>,
+        content_type: &str,
     ) -> Result<PodmanServiceResponse>
     where
         S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
@@ -351,29 +539,26 @@ impl PodmanService {
 
         let mut request_builder = Request::builder()
             .method(method)
-            .uri(Into::<HyperUri>::into(Uri::new(
-                &self.path,
-                endpoint.as_str(),
-            )));
+            .uri(self.request_uri(&endpoint).await?);
         if let Some(header) = header {
             for (key, value) in header {
                 request_builder = request_builder.header(key.as_str(), value.as_str());
             }
         };
         let request = if let Some(body) = body {
-            request_builder = request_builder.header("content-type", "application/x-tar");
+            request_builder = request_builder.header("content-type", content_type);
             let body = body.map_err(Into::into);
             request_builder.body(Body::wrap_stream(body))?
         } else {
             request_builder.body(Body::empty())?
         };
 
-        #[cfg(feature = "enable-tracing")]
+        #[cfg(feature = "tracing")]
         debug!(?request);
 
         let response = self.client.request(request).await?;
 
-        #[cfg(feature = "enable-tracing")]
+        #[cfg(feature = "tracing")]
         debug!(?response);
 
         let (parts, body) = response.into_parts();
@@ -384,3 +569,207 @@ impl PodmanService {
         ))
     }
 }
+
+/// Splits the output of an attached process into frames. If `tty` is set, podman sends the raw
+/// terminal output without any framing, every received chunk is then treated as stdout.
+pub(crate) fn demultiplex<R>(reader: R, tty: bool) -> impl Stream<Item = Result<StreamFrame>>
+where
+    R: AsyncRead + Unpin,
+{
+    let stream = if tty {
+        Either::Left(
+            FramedRead::new(reader, BytesCodec).map_ok(|payload| StreamFrame {
+                stream_type: StreamType::Stdout,
+                payload: payload.to_vec(),
+            }),
+        )
+    } else {
+        Either::Right(FramedRead::new(reader, MultiplexedStreamCodec))
+    };
+    stream.map_err(|e| e.into())
+}
+
+/// Origin of a frame in a multiplexed stream.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum StreamType {
+    Stdin,
+    Stdout,
+    Stderr,
+}
+
+/// A single frame of a multiplexed stream.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct StreamFrame {
+    pub stream_type: StreamType,
+    pub payload: Vec<u8>,
+}
+
+/// Decodes the multiplexed stream format podman uses for attached output of containers without
+/// a tty. Every frame starts with an 8 byte header: the first byte identifies the stream
+/// (0: stdin, 1: stdout, 2: stderr), the last 4 bytes contain the payload length (big endian).
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct MultiplexedStreamCodec;
+
+impl MultiplexedStreamCodec {
+    const HEADER_LENGTH: usize = 8;
+}
+
+impl Decoder for MultiplexedStreamCodec {
+    type Item = StreamFrame;
+    type Error = std::io::Error;
+
+    fn decode(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> std::result::Result<Option<Self::Item>, Self::Error> {
+        if src.len() < Self::HEADER_LENGTH {
+            return Ok(None);
+        }
+        let payload_length = u32::from_be_bytes([src[4], src[5], src[6], src[7]]) as usize;
+        let frame_length = Self::HEADER_LENGTH + payload_length;
+        if src.len() < frame_length {
+            src.reserve(frame_length - src.len());
+            return Ok(None);
+        }
+
+        let stream_type = match src[0] {
+            0 => StreamType::Stdin,
+            1 => StreamType::Stdout,
+            2 => StreamType::Stderr,
+            unknown => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("unknown stream type {unknown} in multiplexed stream header"),
+                ))
+            }
+        };
+        let mut frame = src.split_to(frame_length);
+        let payload = frame.split_off(Self::HEADER_LENGTH).to_vec();
+        Ok(Some(StreamFrame {
+            stream_type,
+            payload,
+        }))
+    }
+}
+
+/// Decodes container logs. Podman sends logs in the multiplexed stream format, one line per
+/// frame. If requested, every line is prefixed with its timestamp followed by a space.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct LogFrameCodec {
+    pub timestamps: bool,
+}
+
+impl Decoder for LogFrameCodec {
+    type Item = LogFrame;
+    type Error = std::io::Error;
+
+    fn decode(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> std::result::Result<Option<Self::Item>, Self::Error> {
+        let frame = match MultiplexedStreamCodec.decode(src)? {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+        let stream = match frame.stream_type {
+            StreamType::Stdin | StreamType::Stdout => LogStream::Stdout,
+            StreamType::Stderr => LogStream::Stderr,
+        };
+        let payload = frame.payload;
+        let separator = payload.iter().position(|byte| *byte == b' ');
+        let (timestamp, bytes) = match separator {
+            Some(position) if self.timestamps => {
+                let timestamp = String::from_utf8(payload[..position].to_vec())
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                (Some(timestamp), payload[position + 1..].to_vec())
+            }
+            _ => (None, payload),
+        };
+        Ok(Some(LogFrame {
+            stream,
+            timestamp,
+            bytes,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod multiplexed_stream_codec {
+    use super::*;
+
+    #[test]
+    fn decode_frames() {
+        let mut src = BytesMut::new();
+        src.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 6]);
+        src.extend_from_slice(b"hello\n");
+        src.extend_from_slice(&[2, 0, 0, 0, 0, 0, 0, 4]);
+        src.extend_from_slice(b"err");
+
+        let mut codec = MultiplexedStreamCodec;
+        assert_eq!(
+            Some(StreamFrame {
+                stream_type: StreamType::Stdout,
+                payload: b"hello\n".to_vec(),
+            }),
+            codec.decode(&mut src).expect("Error decoding stdout frame")
+        );
+        // The stderr payload is incomplete.
+        assert_eq!(
+            None,
+            codec
+                .decode(&mut src)
+                .expect("Error decoding partial frame")
+        );
+        src.extend_from_slice(b"\n");
+        assert_eq!(
+            Some(StreamFrame {
+                stream_type: StreamType::Stderr,
+                payload: b"err\n".to_vec(),
+            }),
+            codec.decode(&mut src).expect("Error decoding stderr frame")
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn decode_unknown_stream_type() {
+        let mut src = BytesMut::from(&[7, 0, 0, 0, 0, 0, 0, 0][..]);
+        assert!(MultiplexedStreamCodec.decode(&mut src).is_err());
+    }
+
+    #[test]
+    fn decode_log_frame_with_timestamp() {
+        let line = b"2023-05-02T10:42:00.123456789+02:00 Container still running.\n";
+        let mut src = BytesMut::from(&[2, 0, 0, 0, 0, 0, 0, line.len() as u8][..]);
+        src.extend_from_slice(line);
+
+        assert_eq!(
+            Some(LogFrame {
+                stream: LogStream::Stderr,
+                timestamp: Some(String::from("2023-05-02T10:42:00.123456789+02:00")),
+                bytes: b"Container still running.\n".to_vec(),
+            }),
+            LogFrameCodec { timestamps: true }
+                .decode(&mut src)
+                .expect("Error decoding log frame")
+        );
+    }
+
+    #[test]
+    fn decode_log_frame_without_timestamp() {
+        let line = b"Container still running.\n";
+        let mut src = BytesMut::from(&[1, 0, 0, 0, 0, 0, 0, line.len() as u8][..]);
+        src.extend_from_slice(line);
+
+        assert_eq!(
+            Some(LogFrame {
+                stream: LogStream::Stdout,
+                timestamp: None,
+                bytes: line.to_vec(),
+            }),
+            LogFrameCodec { timestamps: false }
+                .decode(&mut src)
+                .expect("Error decoding log frame")
+        );
+    }
+}
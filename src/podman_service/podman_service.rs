@@ -1,55 +1,172 @@
 use crate::containers::Containers;
+use crate::error::Result;
+use crate::exec::Exec;
 use crate::images::Images;
+use crate::kube::Kube;
+use crate::manifests::Manifests;
 use crate::networks::Networks;
+use crate::podman_service::api_version::ApiVersion;
+use crate::podman_service::connections::ConnectionSources;
+use crate::podman_service::local_service::{LocalPodmanService, LocalServiceConfig};
+#[cfg(feature = "tls")]
+use crate::podman_service::transport::TlsConfig;
+use crate::podman_service::transport::{PodmanConnector, SshConfig, Transport};
 use crate::pods::Pods;
+use crate::secrets::Secrets;
 use crate::system::System;
 use crate::volumes::Volumes;
 use hyper::Client;
-use hyperlocal::{UnixClientExt, UnixConnector};
-use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
 
-/// The podman service. Only a unix socket is supported.
+/// The podman service, reached via unix socket, tcp (optionally with TLS) or ssh.
 #[derive(Debug, Clone)]
 pub struct PodmanService {
-    pub(crate) path: String,
-    pub(crate) client: Client<UnixConnector>,
+    pub(crate) transport: Arc<Transport>,
+    pub(crate) client: Client<PodmanConnector>,
+    /// Pinned or negotiated on the first request, shared by clones.
+    pub(crate) api_version: Arc<OnceCell<ApiVersion>>,
 }
 
-/// `path` expects a path to the podman socket as `&str`.
 impl PodmanService {
+    /// `path` expects a path to the podman socket as `&str`.
     pub fn new(path: &str) -> Self {
+        Self::with_transport(Transport::Unix(path.into()))
+    }
+
+    /// Connects to the podman service at `uri`, either `unix:///path/to/podman.sock` or
+    /// `tcp://host:port` as passed to `podman system service`, or
+    /// `ssh://[user@]host[:port]/path/to/podman.sock` with the default `SshConfig`.
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        Ok(Self::with_transport(Transport::from_uri(uri)?))
+    }
+
+    /// Connects to the podman service at `uri` (`ssh://[user@]host[:port]/path/to/podman.sock`)
+    /// through an ssh child process, like `podman --remote` connections.
+    pub fn from_uri_with_ssh(uri: &str, ssh_config: &SshConfig) -> Result<Self> {
+        Ok(Self::with_transport(Transport::from_uri_with_ssh(
+            uri, ssh_config,
+        )?))
+    }
+
+    /// Connects to the podman service at `uri` (`tcp://host:port`) over TLS. Requires the `tls`
+    /// feature.
+    #[cfg(feature = "tls")]
+    pub fn from_uri_with_tls(uri: &str, tls_config: &TlsConfig) -> Result<Self> {
+        Ok(Self::with_transport(Transport::from_uri_with_tls(
+            uri, tls_config,
+        )?))
+    }
+
+    /// Connects to the podman service selected like `podman --remote` does: `CONTAINER_HOST`
+    /// (with `CONTAINER_SSHKEY`), `CONTAINER_CONNECTION`, the default connection of
+    /// `podman system connection` from `podman-connections.json` or `containers.conf`, then the
+    /// rootless `$XDG_RUNTIME_DIR/podman/podman.sock` and rootful `/run/podman/podman.sock`.
+    /// `PodtenderError::ServiceNotFound` lists what was tried if none is found.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::with_transport(
+            ConnectionSources::from_env().resolve()?,
+        ))
+    }
+
+    /// Connects to the connection `name` added with `podman system connection add`.
+    pub fn from_connection(name: &str) -> Result<Self> {
+        Ok(Self::with_transport(
+            ConnectionSources::from_env().resolve_connection(name)?,
+        ))
+    }
+
+    /// Starts `podman system service` with `ttl` on a socket in a temporary directory and waits
+    /// until it answers `/_ping`. The service is killed when the returned `LocalPodmanService` is
+    /// dropped.
+    pub async fn spawn_local(ttl: Duration) -> Result<LocalPodmanService> {
+        let config = LocalServiceConfig {
+            ttl,
+            ..Default::default()
+        };
+        Self::spawn_local_with_config(&config).await
+    }
+
+    /// `spawn_local` with a different podman executable or startup timeout. Fails with
+    /// `PodtenderError::LocalServiceStartFailed`, containing the service's stderr, if it exits or
+    /// doesn't become ready in time.
+    pub async fn spawn_local_with_config(
+        config: &LocalServiceConfig,
+    ) -> Result<LocalPodmanService> {
+        LocalPodmanService::spawn(config).await
+    }
+
+    pub(crate) fn with_transport(transport: Transport) -> Self {
+        let transport = Arc::new(transport);
         PodmanService {
-            path: path.to_string(),
-            client: Client::unix(),
+            client: Client::builder().build(PodmanConnector::new(transport.clone())),
+            transport,
+            api_version: Arc::new(OnceCell::new()),
         }
     }
 
-    /// Check whether the podman socket exists.
+    /// Pins the api version of the service instead of querying it with the first request.
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = Arc::new(OnceCell::new_with(Some(api_version)));
+        self
+    }
+
+    /// The api version of the service, queried from `/version` once unless pinned with
+    /// `with_api_version`. Requests use it if it's older than the version podtender targets,
+    /// operations needing a newer version fail with `PodtenderError::UnsupportedByServer`.
+    pub async fn api_version(&self) -> Result<ApiVersion> {
+        self.api_version
+            .get_or_try_init(|| self.negotiate_api_version())
+            .await
+            .copied()
+    }
+
+    /// Check whether the podman socket exists. Always `true` for tcp and ssh connections.
     pub fn check_socket_exists(&self) -> bool {
-        PathBuf::from(&self.path).exists()
+        match self.transport.socket_path() {
+            Some(path) => path.exists(),
+            None => true,
+        }
     }
 
-    pub fn system(&self) -> System {
+    pub fn system(&self) -> System<'_> {
         System::new(self)
     }
 
-    pub fn containers(&self) -> Containers {
+    pub fn containers(&self) -> Containers<'_> {
         Containers::new(self)
     }
 
-    pub fn volumes(&self) -> Volumes {
+    pub fn exec(&self) -> Exec<'_> {
+        Exec::new(self)
+    }
+
+    pub fn volumes(&self) -> Volumes<'_> {
         Volumes::new(self)
     }
 
-    pub fn networks(&self) -> Networks {
+    pub fn networks(&self) -> Networks<'_> {
         Networks::new(self)
     }
 
-    pub fn pods(&self) -> Pods {
+    pub fn pods(&self) -> Pods<'_> {
         Pods::new(self)
     }
 
-    pub fn images(&self) -> Images {
+    pub fn images(&self) -> Images<'_> {
         Images::new(self)
     }
+
+    pub fn manifests(&self) -> Manifests<'_> {
+        Manifests::new(self)
+    }
+
+    pub fn secrets(&self) -> Secrets<'_> {
+        Secrets::new(self)
+    }
+
+    pub fn kube(&self) -> Kube<'_> {
+        Kube::new(self)
+    }
 }
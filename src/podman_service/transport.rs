@@ -0,0 +1,658 @@
+//! Connections to the podman service. Every request is sent to `http://d/...`, the connector
+//! ignores the host and connects to the transport selected when creating the `PodmanService`.
+
+use crate::error::{PodtenderError, Result};
+use hyper::client::connect::{Connected, Connection as HyperConnection};
+use hyper::service::Service;
+use hyper::Uri;
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpStream, UnixStream};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+/// Where the podman service listens.
+#[derive(Clone)]
+pub(crate) enum Transport {
+    Unix(PathBuf),
+    /// `host:port`
+    Tcp(String),
+    /// Unix socket on a remote host, forwarded by an ssh child process per connection.
+    Ssh {
+        /// `user@host` or `host`
+        destination: String,
+        port: Option<u16>,
+        socket_path: String,
+        config: SshConfig,
+    },
+    #[cfg(feature = "tls")]
+    Tls {
+        address: String,
+        server_name: tokio_rustls::rustls::ServerName,
+        config: Arc<tokio_rustls::rustls::ClientConfig>,
+    },
+}
+
+impl std::fmt::Debug for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transport::Unix(path) => f.debug_tuple("Unix").field(path).finish(),
+            Transport::Tcp(address) => f.debug_tuple("Tcp").field(address).finish(),
+            Transport::Ssh {
+                destination,
+                port,
+                socket_path,
+                config,
+            } => f
+                .debug_struct("Ssh")
+                .field("destination", destination)
+                .field("port", port)
+                .field("socket_path", socket_path)
+                .field("config", config)
+                .finish(),
+            #[cfg(feature = "tls")]
+            Transport::Tls {
+                address,
+                server_name,
+                ..
+            } => f
+                .debug_struct("Tls")
+                .field("address", address)
+                .field("server_name", server_name)
+                .finish(),
+        }
+    }
+}
+
+impl Transport {
+    /// Parses `unix:///path/to/podman.sock`, `tcp://host:port` and
+    /// `ssh://[user@]host[:port]/path/to/podman.sock`.
+    pub(crate) fn from_uri(uri: &str) -> Result<Self> {
+        if let Some(path) = uri.strip_prefix("unix://") {
+            if path.starts_with('/') {
+                return Ok(Transport::Unix(PathBuf::from(path)));
+            }
+        } else if let Some(address) = uri.strip_prefix("tcp://") {
+            let address = address.trim_end_matches('/');
+            if host_and_port(address).is_some() {
+                return Ok(Transport::Tcp(address.to_string()));
+            }
+        } else if uri.starts_with("ssh://") {
+            return Transport::from_uri_with_ssh(uri, &SshConfig::default());
+        }
+        Err(PodtenderError::InvalidServiceUri(uri.to_string()))
+    }
+
+    /// Path of the unix socket, `None` for other transports.
+    pub(crate) fn socket_path(&self) -> Option<&PathBuf> {
+        match self {
+            Transport::Unix(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Parses `ssh://[user@]host[:port]/path/to/podman.sock`, the format used by
+    /// `podman system connection`.
+    pub(crate) fn from_uri_with_ssh(uri: &str, ssh_config: &SshConfig) -> Result<Self> {
+        let invalid_uri = || PodtenderError::InvalidServiceUri(uri.to_string());
+        let (authority, socket_path) = uri
+            .strip_prefix("ssh://")
+            .and_then(|rest| rest.find('/').map(|index| rest.split_at(index)))
+            .ok_or_else(invalid_uri)?;
+        let (user, address) = match authority.rsplit_once('@') {
+            Some((user, address)) if !user.is_empty() => (Some(user), address),
+            Some(_) => return Err(invalid_uri()),
+            None => (None, authority),
+        };
+        let (host, port) = match host_and_port(address) {
+            Some((host, port)) => (host, Some(port)),
+            None if !address.is_empty() && !address.contains(':') => (address, None),
+            None => return Err(invalid_uri()),
+        };
+        if socket_path.len() < 2 {
+            return Err(invalid_uri());
+        }
+        let destination = match user {
+            Some(user) => format!("{}@{}", user, host),
+            None => host.to_string(),
+        };
+        Ok(Transport::Ssh {
+            destination,
+            port,
+            socket_path: socket_path.to_string(),
+            config: ssh_config.clone(),
+        })
+    }
+
+    async fn connect(&self) -> io::Result<Connection> {
+        match self {
+            Transport::Unix(path) => Ok(Connection::new(UnixStream::connect(path).await?)),
+            Transport::Tcp(address) => {
+                let stream = TcpStream::connect(address).await?;
+                stream.set_nodelay(true)?;
+                Ok(Connection::new(stream))
+            }
+            Transport::Ssh {
+                destination,
+                port,
+                socket_path,
+                config,
+            } => {
+                let mut command =
+                    Command::new(config.ssh_program.as_deref().unwrap_or("ssh".as_ref()));
+                // forward stdin/stdout to the remote socket, never prompt for passwords
+                command.arg("-W").arg(socket_path);
+                command.args(["-o", "BatchMode=yes"]);
+                if let Some(identity) = &config.identity {
+                    command.arg("-i").arg(identity);
+                }
+                if let Some(port) = port {
+                    command.arg("-p").arg(port.to_string());
+                }
+                command.args(&config.extra_args).arg("--").arg(destination);
+                let mut child = command
+                    .stdin(std::process::Stdio::piped())
+                    .stdout(std::process::Stdio::piped())
+                    .kill_on_drop(true)
+                    .spawn()?;
+                let stdin = child.stdin.take().expect("stdin is piped");
+                let stdout = child.stdout.take().expect("stdout is piped");
+                Ok(Connection::new(SshStream {
+                    _child: child,
+                    stdin,
+                    stdout,
+                }))
+            }
+            #[cfg(feature = "tls")]
+            Transport::Tls {
+                address,
+                server_name,
+                config,
+            } => {
+                let stream = TcpStream::connect(address).await?;
+                stream.set_nodelay(true)?;
+                let stream = tokio_rustls::TlsConnector::from(config.clone())
+                    .connect(server_name.clone(), stream)
+                    .await?;
+                Ok(Connection::new(stream))
+            }
+        }
+    }
+}
+
+/// Splits `host:port`, IPv6 hosts are expected in brackets, e.g. `[::1]:8080`.
+fn host_and_port(address: &str) -> Option<(&str, u16)> {
+    let (host, port) = address.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    if host.is_empty() || host.contains('/') {
+        None
+    } else {
+        Some((host, port))
+    }
+}
+
+/// Options for connections over ssh. The `ssh` executable is used, so `~/.ssh/config`, the
+/// ssh agent and `known_hosts` apply as they do for `podman --remote`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SshConfig {
+    /// Private key passed to ssh with `-i`.
+    pub identity: Option<PathBuf>,
+    /// The ssh executable, defaults to `ssh` from `PATH`.
+    pub ssh_program: Option<PathBuf>,
+    /// Additional arguments passed to ssh, e.g. `["-o", "StrictHostKeyChecking=accept-new"]`.
+    pub extra_args: Vec<String>,
+}
+
+/// Certificates used to connect to a podman service over TLS. Requires the `tls` feature.
+#[cfg(feature = "tls")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// PEM file with the CA certificates the server's certificate is verified with.
+    pub ca_cert: PathBuf,
+    /// PEM file with the client certificate chain, requires `client_key`.
+    pub client_cert: Option<PathBuf>,
+    /// PEM file with the client's private key (PKCS#8, PKCS#1 or SEC1).
+    pub client_key: Option<PathBuf>,
+    /// Name the server's certificate has to be valid for, defaults to the host of the uri.
+    pub server_name: Option<String>,
+}
+
+#[cfg(feature = "tls")]
+impl Transport {
+    /// Parses `tcp://host:port` and connects to it with TLS.
+    pub(crate) fn from_uri_with_tls(uri: &str, tls_config: &TlsConfig) -> Result<Self> {
+        use tokio_rustls::rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore};
+
+        let address = match Transport::from_uri(uri)? {
+            Transport::Tcp(address) => address,
+            _ => return Err(PodtenderError::InvalidServiceUri(uri.to_string())),
+        };
+        let server_name = match &tls_config.server_name {
+            Some(server_name) => server_name.as_str(),
+            None => host_and_port(&address)
+                .map(|(host, _)| host)
+                .unwrap_or_default(),
+        };
+        let server_name = tokio_rustls::rustls::ServerName::try_from(server_name)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+        let mut root_certs = RootCertStore::empty();
+        for cert in read_pem_certs(&tls_config.ca_cert)? {
+            root_certs
+                .add(&cert)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        }
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(root_certs);
+        let config = match (&tls_config.client_cert, &tls_config.client_key) {
+            (Some(client_cert), Some(client_key)) => {
+                let key = read_pem_private_key(client_key)?;
+                builder
+                    .with_client_auth_cert(read_pem_certs(client_cert)?, key)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+            }
+            (None, None) => builder.with_no_client_auth(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "client_cert and client_key have to be set together",
+                )
+                .into())
+            }
+        };
+
+        fn read_pem_certs(path: &PathBuf) -> io::Result<Vec<Certificate>> {
+            let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+            Ok(rustls_pemfile::certs(&mut reader)?
+                .into_iter()
+                .map(Certificate)
+                .collect())
+        }
+
+        fn read_pem_private_key(path: &PathBuf) -> io::Result<PrivateKey> {
+            use rustls_pemfile::Item;
+            let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+            while let Some(item) = rustls_pemfile::read_one(&mut reader)? {
+                if let Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) = item {
+                    return Ok(PrivateKey(key));
+                }
+            }
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("no private key found in {}", path.display()),
+            ))
+        }
+
+        Ok(Transport::Tls {
+            address,
+            server_name,
+            config: Arc::new(config),
+        })
+    }
+}
+
+/// Connects hyper to the transport.
+#[derive(Debug, Clone)]
+pub(crate) struct PodmanConnector {
+    transport: Arc<Transport>,
+}
+
+impl PodmanConnector {
+    pub(crate) fn new(transport: Arc<Transport>) -> Self {
+        PodmanConnector { transport }
+    }
+}
+
+impl Service<Uri> for PodmanConnector {
+    type Response = Connection;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Connection>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let transport = self.transport.clone();
+        Box::pin(async move { transport.connect().await })
+    }
+}
+
+trait Io: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Io for T {}
+
+/// stdin and stdout of the ssh child process, the process is killed when the connection is
+/// dropped.
+struct SshStream {
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+}
+
+impl AsyncRead for SshStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stdout).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SshStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stdin).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stdin).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stdin).poll_shutdown(cx)
+    }
+}
+
+/// A connection of any transport.
+pub(crate) struct Connection(Box<dyn Io>);
+
+impl Connection {
+    fn new(io: impl AsyncRead + AsyncWrite + Send + Unpin + 'static) -> Self {
+        Connection(Box::new(io))
+    }
+}
+
+impl HyperConnection for Connection {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+impl AsyncRead for Connection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Connection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.0).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.0).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.0.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.0).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::podman_service::{ApiVersion, PodmanService};
+    use crate::volumes::parameter_types::VolumeExistsParameter;
+    use hyper::server::conn::Http;
+    use hyper::service::service_fn;
+    use hyper::{Body, Request, Response, StatusCode};
+    use std::path::Path;
+    use tokio::net::{TcpListener, UnixListener};
+
+    /// Answers `exists` requests for the volume `test` with 204, everything else with 404.
+    fn serve(io: impl AsyncRead + AsyncWrite + Send + Unpin + 'static) {
+        tokio::spawn(async move {
+            let service = service_fn(|request: Request<Body>| async move {
+                let status = if request
+                    .uri()
+                    .path()
+                    .ends_with("/libpod/volumes/test/exists")
+                {
+                    StatusCode::NO_CONTENT
+                } else {
+                    StatusCode::NOT_FOUND
+                };
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = status;
+                Ok::<_, hyper::Error>(response)
+            });
+            let _ = Http::new().serve_connection(io, service).await;
+        });
+    }
+
+    async fn volume_exists(podman_service: &PodmanService) -> bool {
+        // the stand-in doesn't answer `/version`
+        let podman_service = podman_service
+            .clone()
+            .with_api_version(ApiVersion::new(4, 4, 0));
+        podman_service
+            .volumes()
+            .exists(VolumeExistsParameter {
+                volume_name: String::from("test"),
+            })
+            .await
+            .is_ok()
+    }
+
+    #[test]
+    fn parse_uris() {
+        assert!(matches!(
+            Transport::from_uri("unix:///run/podman/podman.sock"),
+            Ok(Transport::Unix(path)) if path == Path::new("/run/podman/podman.sock")
+        ));
+        assert!(matches!(
+            Transport::from_uri("tcp://localhost:8080"),
+            Ok(Transport::Tcp(address)) if address == "localhost:8080"
+        ));
+        assert!(matches!(
+            Transport::from_uri("tcp://[::1]:8080/"),
+            Ok(Transport::Tcp(address)) if address == "[::1]:8080"
+        ));
+        assert!(matches!(
+            Transport::from_uri("ssh://core@localhost:2222/run/user/1000/podman/podman.sock"),
+            Ok(Transport::Ssh { destination, port: Some(2222), socket_path, .. })
+                if destination == "core@localhost" && socket_path == "/run/user/1000/podman/podman.sock"
+        ));
+        assert!(matches!(
+            Transport::from_uri("ssh://localhost/run/podman/podman.sock"),
+            Ok(Transport::Ssh { destination, port: None, .. }) if destination == "localhost"
+        ));
+        for uri in [
+            "/run/podman/podman.sock",
+            "ssh://core@localhost",
+            "ssh://core@localhost/",
+            "ssh://@localhost/run/podman/podman.sock",
+            "unix://podman.sock",
+            "tcp://localhost",
+            "tcp://:8080",
+            "http://localhost:8080",
+        ] {
+            assert!(matches!(
+                Transport::from_uri(uri),
+                Err(PodtenderError::InvalidServiceUri(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn request_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("tcp://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                serve(stream);
+            }
+        });
+
+        let podman_service = PodmanService::from_uri(&uri).unwrap();
+        assert!(podman_service.check_socket_exists());
+        assert!(volume_exists(&podman_service).await);
+    }
+
+    #[tokio::test]
+    async fn request_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("podtender-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                serve(stream);
+            }
+        });
+
+        let podman_service =
+            PodmanService::from_uri(&format!("unix://{}", path.display())).unwrap();
+        assert!(podman_service.check_socket_exists());
+        let exists = volume_exists(&podman_service).await;
+        let _ = std::fs::remove_file(&path);
+        assert!(exists);
+    }
+
+    /// Stand-in for `ssh -W`: records its arguments and connects stdin/stdout to the socket.
+    const FAKE_SSH: &str = r#"#!/usr/bin/env python3
+import os, socket, sys, threading
+args = sys.argv[1:]
+with open(sys.argv[0] + ".args", "w") as file:
+    file.write("\n".join(args))
+sock = socket.socket(socket.AF_UNIX)
+sock.connect(args[args.index("-W") + 1])
+def forward_stdin():
+    while data := os.read(0, 65536):
+        sock.sendall(data)
+    sock.shutdown(socket.SHUT_WR)
+threading.Thread(target=forward_stdin, daemon=True).start()
+while data := sock.recv(65536):
+    os.write(1, data)
+"#;
+
+    #[tokio::test]
+    async fn request_over_ssh() {
+        use std::os::unix::fs::PermissionsExt;
+
+        if std::process::Command::new("python3")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            eprintln!("skipping request_over_ssh, python3 is required for the ssh stand-in");
+            return;
+        }
+        let dir = std::env::temp_dir().join(format!("podtender-ssh-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ssh_program = dir.join("ssh");
+        std::fs::write(&ssh_program, FAKE_SSH).unwrap();
+        std::fs::set_permissions(&ssh_program, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let socket_path = dir.join("podman.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                serve(stream);
+            }
+        });
+
+        let ssh_config = SshConfig {
+            identity: Some(PathBuf::from("/home/core/.ssh/id_ed25519")),
+            ssh_program: Some(ssh_program.clone()),
+            extra_args: Vec::new(),
+        };
+        let uri = format!("ssh://core@remote.example:2222{}", socket_path.display());
+        let podman_service = PodmanService::from_uri_with_ssh(&uri, &ssh_config).unwrap();
+        let exists = volume_exists(&podman_service).await;
+        let args = std::fs::read_to_string(dir.join("ssh.args"));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(exists);
+        let args = args.unwrap();
+        let args: Vec<_> = args.lines().collect();
+        assert_eq!(
+            vec![
+                "-W",
+                socket_path.to_str().unwrap(),
+                "-o",
+                "BatchMode=yes",
+                "-i",
+                "/home/core/.ssh/id_ed25519",
+                "-p",
+                "2222",
+                "--",
+                "core@remote.example",
+            ],
+            args
+        );
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn request_over_tls() {
+        use tokio_rustls::rustls::{Certificate, PrivateKey, ServerConfig};
+
+        let cert = rcgen::generate_simple_self_signed(vec![String::from("localhost")]).unwrap();
+        let dir = std::env::temp_dir().join(format!("podtender-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ca_cert = dir.join("ca.pem");
+        std::fs::write(&ca_cert, cert.serialize_pem().unwrap()).unwrap();
+
+        let server_config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![Certificate(cert.serialize_der().unwrap())],
+                PrivateKey(cert.serialize_private_key_der()),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(server_config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("tcp://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                if let Ok(stream) = acceptor.accept(stream).await {
+                    serve(stream);
+                }
+            }
+        });
+
+        let tls_config = TlsConfig {
+            ca_cert,
+            server_name: Some(String::from("localhost")),
+            ..Default::default()
+        };
+        let podman_service = PodmanService::from_uri_with_tls(&uri, &tls_config).unwrap();
+        let exists = volume_exists(&podman_service).await;
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(exists);
+    }
+}
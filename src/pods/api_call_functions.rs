@@ -1,11 +1,13 @@
+use crate::containers::parameter_types::GenerateSystemdParameter;
 use crate::error::Result;
 use crate::podman_service::PodmanService;
 use crate::pods::parameter_types::*;
 use crate::pods::response_types::*;
 use crate::utils;
 use futures::Stream;
+use std::collections::HashMap;
 use std::convert::TryInto;
-#[cfg(feature = "enable-tracing")]
+#[cfg(feature = "tracing")]
 use tracing::instrument;
 
 /// Pod operations.
@@ -20,25 +22,21 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodCreateLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.create"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.create"))]
     pub async fn create(&self, parameter: CreatePodParameter) -> Result<CreatePodResponse> {
-        let endpoint = utils::create_endpoint("/libpod/pods/create");
+        let endpoint = "/libpod/pods/create";
         let body = serde_json::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, None, None, Some(body))
+            .post_request(endpoint, None, None, Some(body))
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodDeleteLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.remove"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.remove"))]
     pub async fn remove(&self, parameter: RemovePodParameter) -> Result<RemovePodResponse> {
-        let endpoint = format!(
-            "{}{}",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}", parameter.pod_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -48,13 +46,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodExistsLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.exists"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.exists"))]
     pub async fn exists(&self, parameter: PodExistsParameter) -> Result<()> {
-        let endpoint = format!(
-            "{}{}/exists",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/exists", parameter.pod_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -63,13 +57,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodInspectLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.inspect"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.inspect"))]
     pub async fn inspect(&self, parameter: InspectPodParameter) -> Result<InspectPodResponse> {
-        let endpoint = format!(
-            "{}{}/json",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/json", parameter.pod_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -78,13 +68,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodKillLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.kill"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.kill"))]
     pub async fn kill(&self, parameter: KillPodParameter) -> Result<KillPodResponse> {
-        let endpoint = format!(
-            "{}{}/kill",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/kill", parameter.pod_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -94,13 +80,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodPauseLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.pause"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.pause"))]
     pub async fn pause(&self, parameter: PausePodParameter) -> Result<PausePodResponse> {
-        let endpoint = format!(
-            "{}{}/pause",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/pause", parameter.pod_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -109,13 +91,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodRestartLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.restart"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.restart"))]
     pub async fn restart(&self, parameter: RestartPodParameter) -> Result<RestartPodResponse> {
-        let endpoint = format!(
-            "{}{}/restart",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/restart", parameter.pod_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -124,13 +102,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodStartLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.start"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.start"))]
     pub async fn start(&self, parameter: StartPodParameter) -> Result<StartPodResponse> {
-        let endpoint = format!(
-            "{}{}/start",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/start", parameter.pod_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -139,13 +113,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodStopLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.stop"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.stop"))]
     pub async fn stop(&self, parameter: StopPodParameter) -> Result<StopPodResponse> {
-        let endpoint = format!(
-            "{}{}/stop",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/stop", parameter.pod_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -154,16 +124,12 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodTopLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.list_processes"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.list_processes"))]
     pub async fn list_processes(
         &self,
         parameter: ListPodProcessesParameter,
     ) -> Result<ListPodProcessesResponse> {
-        let endpoint = format!(
-            "{}{}/top",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/top", parameter.pod_name);
 
         let parameter = ListPodProcessesParameter {
             stream: Some(false),
@@ -180,18 +146,14 @@ impl<'service> Pods<'service> {
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodTopLibpod>
     #[cfg_attr(
-        feature = "enable-tracing",
+        feature = "tracing",
         instrument(name = "Pods.list_processes_streaming")
     )]
     pub async fn list_processes_streaming(
         &self,
         parameter: ListPodProcessesParameter,
     ) -> Result<impl Stream<Item = Result<ListPodProcessesResponse>>> {
-        let endpoint = format!(
-            "{}{}/top",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/top", parameter.pod_name);
         let parameter = ListPodProcessesParameter {
             stream: Some(true),
             ..parameter
@@ -206,13 +168,9 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodUnpauseLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.unpause"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.unpause"))]
     pub async fn unpause(&self, parameter: UnpausePodParameter) -> Result<UnpausePodResponse> {
-        let endpoint = format!(
-            "{}{}/unpause",
-            utils::create_endpoint("/libpod/pods/"),
-            parameter.pod_name
-        );
+        let endpoint = format!("/libpod/pods/{}/unpause", parameter.pod_name);
         let service_response = self
             .podman_service
             .post_request(&endpoint, None, None, None)
@@ -221,37 +179,37 @@ impl<'service> Pods<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodListLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.list"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.list"))]
     pub async fn list(&self, parameter: ListPodsParameter) -> Result<Vec<ListPodsResponseEntry>> {
         let parameter: ListPodsParameterQuery = parameter.try_into()?;
-        let endpoint = utils::create_endpoint("/libpod/pods/json");
+        let endpoint = "/libpod/pods/json";
 
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .get_request(&endpoint, Some(query), None, None)
+            .get_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodPruneLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.prune"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.prune"))]
     pub async fn prune(&self) -> Result<Vec<PrunePodsResponse>> {
-        let endpoint = utils::create_endpoint("/libpod/pods/prune");
+        let endpoint = "/libpod/pods/prune";
         let service_response = self
             .podman_service
-            .post_request(&endpoint, None, None, None)
+            .post_request(endpoint, None, None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/PodStatsAllLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Pods.stats"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.stats"))]
     pub async fn stats(
         &self,
         parameter: PodStatsParameter,
     ) -> Result<impl Stream<Item = Result<Vec<PodStatsResponse>>>> {
-        let endpoint = format!("{}stats", utils::create_endpoint("/libpod/pods/"),);
+        let endpoint = "/libpod/pods/stats";
 
         // Start workaround cause podman only supports query arrays in this format:
         // containers=container1&containers=container2
@@ -266,13 +224,9 @@ impl<'service> Pods<'service> {
             }
             let temp_query = TempQuery { all: parameter.all };
 
-            if parameter.names_or_ids.is_none() {
-                serde_qs::to_string(&temp_query)?
-            } else {
-                let temp_query = serde_qs::to_string(&temp_query)?;
-                if parameter.names_or_ids.as_ref().unwrap().is_empty() {
-                    temp_query
-                } else {
+            let temp_query = serde_qs::to_string(&temp_query)?;
+            match parameter.names_or_ids {
+                Some(names_or_ids) if !names_or_ids.is_empty() => {
                     #[skip_serializing_none]
                     #[derive(Serialize, Debug)]
                     struct TempNamesOrIDs {
@@ -280,7 +234,7 @@ impl<'service> Pods<'service> {
                         names_or_ids: String,
                     }
                     let mut names_or_ids_string = String::new();
-                    for names_or_ids in parameter.names_or_ids.unwrap() {
+                    for names_or_ids in names_or_ids {
                         let temp_name_or_ids = TempNamesOrIDs { names_or_ids };
                         names_or_ids_string.push_str(&serde_qs::to_string(&temp_name_or_ids)?);
                         names_or_ids_string.push('&');
@@ -288,6 +242,7 @@ impl<'service> Pods<'service> {
                     names_or_ids_string.push_str(&temp_query);
                     names_or_ids_string
                 }
+                _ => temp_query,
             }
         };
 
@@ -295,8 +250,22 @@ impl<'service> Pods<'service> {
 
         let (_status_code, result_stream, _header_map) = self
             .podman_service
-            .get_json_stream(&endpoint, Some(query), None, None)
+            .get_json_stream(endpoint, Some(query), None, None)
             .await?;
         Ok(result_stream)
     }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/GenerateSystemdLibpod>
+    /// Returns the unit files of the pod and its containers by service name without the
+    /// `.service` suffix, e.g. `pod-name` and `container-name`.
+    #[cfg_attr(feature = "tracing", instrument(name = "Pods.generate_systemd"))]
+    pub async fn generate_systemd(
+        &self,
+        parameter: GenerateSystemdParameter,
+    ) -> Result<HashMap<String, String>> {
+        self.podman_service
+            .containers()
+            .generate_systemd(parameter)
+            .await
+    }
 }
@@ -0,0 +1,107 @@
+use crate::error::{PodtenderError, Result};
+use crate::podman_service::{ApiVersion, PodmanService};
+use crate::secrets::parameter_types::*;
+use crate::secrets::response_types::*;
+use crate::utils;
+use hyper::body::Bytes;
+use std::convert::TryInto;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+
+/// Secret operations.
+#[derive(Debug)]
+pub struct Secrets<'service> {
+    podman_service: &'service PodmanService,
+}
+
+impl<'service> Secrets<'service> {
+    pub(crate) fn new(podman_service: &'service PodmanService) -> Self {
+        Secrets { podman_service }
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SecretCreateLibpod>
+    /// Creates a secret containing the data of `data_stream`.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(name = "Secrets.create", skip(data_stream))
+    )]
+    pub async fn create<S, O, E>(
+        &self,
+        parameter: CreateSecretParameter,
+        data_stream: S,
+    ) -> Result<CreateSecretResponse>
+    where
+        S: futures::Stream<Item = std::result::Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<PodtenderError> + Send + 'static,
+    {
+        let parameter: CreateSecretParameterQuery = parameter.try_into()?;
+        let endpoint = "/libpod/secrets/create";
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .post_send_data_chunks_stream(endpoint, Some(query), None, Some(data_stream))
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SecretExistsLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Secrets.exists"))]
+    pub async fn exists(&self, parameter: SecretExistsParameter) -> Result<()> {
+        let endpoint = format!("/libpod/secrets/{}/exists", parameter.secret_name);
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, None, None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SecretInspectLibpod>
+    /// `show_secret` requires podman 4.5.
+    #[cfg_attr(feature = "tracing", instrument(name = "Secrets.inspect"))]
+    pub async fn inspect(
+        &self,
+        parameter: InspectSecretParameter,
+    ) -> Result<InspectSecretResponse> {
+        if parameter.show_secret == Some(true) {
+            self.podman_service
+                .require_api_version(ApiVersion::new(4, 5, 0))
+                .await?;
+        }
+        let endpoint = format!("/libpod/secrets/{}/json", parameter.secret_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .get_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SecretListLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Secrets.list"))]
+    pub async fn list(
+        &self,
+        parameter: ListSecretsParameter,
+    ) -> Result<Vec<ListSecretsResponseEntry>> {
+        let parameter: ListSecretsParameterQuery = parameter.try_into()?;
+        let endpoint = "/libpod/secrets/json";
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .get_request(endpoint, Some(query), None, None)
+            .await?;
+        utils::deserialize_service_response(service_response)
+    }
+
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SecretDeleteLibpod>
+    #[cfg_attr(feature = "tracing", instrument(name = "Secrets.remove"))]
+    pub async fn remove(&self, parameter: RemoveSecretParameter) -> Result<()> {
+        let endpoint = format!("/libpod/secrets/{}", parameter.secret_name);
+        let query = serde_qs::to_string(&parameter)?;
+        let service_response = self
+            .podman_service
+            .delete_request(&endpoint, Some(query), None, None)
+            .await?;
+        utils::check_service_response_for_error(service_response)
+    }
+}
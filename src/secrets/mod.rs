@@ -0,0 +1,10 @@
+//! <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#tag/secrets>
+
+mod api_call_functions;
+
+/// Parameter types for secret operations.
+pub mod parameter_types;
+/// Response types for secret operations.
+pub mod response_types;
+
+pub use api_call_functions::*;
@@ -0,0 +1,174 @@
+use crate::error::PodtenderError;
+#[cfg(any(test, feature = "examples"))]
+use crate::example_values_trait::ExampleValues;
+use crate::utils;
+#[cfg(feature = "builder")]
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct CreateSecretParameter {
+    pub name: String,
+    /// Secret driver, podman defaults to `file`.
+    pub driver: Option<String>,
+    pub driver_options: Option<HashMap<String, String>>,
+    pub labels: Option<HashMap<String, String>>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for CreateSecretParameter {
+    fn example() -> Self {
+        let mut labels = HashMap::new();
+        labels.insert(String::from("podtender"), String::from("example"));
+        Self {
+            name: String::from("create_secret_parameter"),
+            driver: Some(String::from("file")),
+            driver_options: None,
+            labels: Some(labels),
+        }
+    }
+}
+
+/// Internal representation of `CreateSecretParameter` since the maps have to be serialized to
+/// json before they can be serialized to query. `TryInto` tries to perform the serialisation
+/// into json.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct CreateSecretParameterQuery {
+    pub name: String,
+    pub driver: Option<String>,
+    pub driveropts: Option<String>,
+    pub labels: Option<String>,
+}
+
+impl TryFrom<CreateSecretParameter> for CreateSecretParameterQuery {
+    type Error = PodtenderError;
+    fn try_from(param: CreateSecretParameter) -> Result<Self, Self::Error> {
+        let driveropts = param
+            .driver_options
+            .map(|driver_options| serde_json::to_string(&driver_options))
+            .transpose()?;
+        let labels = param
+            .labels
+            .map(|labels| serde_json::to_string(&labels))
+            .transpose()?;
+        Ok(CreateSecretParameterQuery {
+            name: param.name,
+            driver: param.driver,
+            driveropts,
+            labels,
+        })
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct ListSecretsParameter {
+    /// Supported filters are `name`, `id`, `label` and `driver`.
+    pub filters: Option<HashMap<String, Vec<String>>>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for ListSecretsParameter {
+    fn example() -> Self {
+        let mut filter_map: HashMap<String, Vec<String>> = HashMap::new();
+        filter_map.insert(
+            String::from("name"),
+            vec![String::from("list_secrets_parameter")],
+        );
+        Self {
+            filters: Some(filter_map),
+        }
+    }
+}
+
+/// Internal representation of `ListSecretsParameter` since filters can't be serialized in a single step.
+/// It needs to be serialized to json, then to query. `TryInto` tries to perform the serialisation into json.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct ListSecretsParameterQuery {
+    pub filters: Option<String>,
+}
+
+impl TryFrom<ListSecretsParameter> for ListSecretsParameterQuery {
+    type Error = PodtenderError;
+    fn try_from(param: ListSecretsParameter) -> Result<Self, Self::Error> {
+        let filters = utils::convert_from_map_to_json_string(param.filters)?;
+        Ok(ListSecretsParameterQuery { filters })
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct InspectSecretParameter {
+    /// Name or id of the secret.
+    #[serde(skip_serializing)]
+    pub secret_name: String,
+    /// Include the secret's data in the response.
+    #[serde(rename = "showsecret")]
+    pub show_secret: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for InspectSecretParameter {
+    fn example() -> Self {
+        Self {
+            secret_name: String::from("inspect_secret_parameter"),
+            show_secret: Some(true),
+        }
+    }
+}
+
+//query
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct RemoveSecretParameter {
+    /// Name or id of the secret.
+    #[serde(skip_serializing)]
+    pub secret_name: String,
+    /// Remove all secrets, `secret_name` is ignored.
+    pub all: Option<bool>,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for RemoveSecretParameter {
+    fn example() -> Self {
+        Self {
+            secret_name: String::from("remove_secret_parameter"),
+            all: Some(false),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+pub struct SecretExistsParameter {
+    /// Name or id of the secret.
+    pub secret_name: String,
+}
+
+#[cfg(any(test, feature = "examples"))]
+impl ExampleValues for SecretExistsParameter {
+    fn example() -> Self {
+        Self {
+            secret_name: String::from("secret_exists_parameter"),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CreateSecretResponse {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InspectSecretResponse {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    #[serde(rename = "CreatedAt")]
+    pub created_at: Option<String>,
+    #[serde(rename = "UpdatedAt")]
+    pub updated_at: Option<String>,
+    #[serde(rename = "Spec")]
+    pub spec: Option<SecretSpec>,
+    /// Only set if requested with `InspectSecretParameter::show_secret`.
+    #[serde(rename = "SecretData")]
+    pub secret_data: Option<String>,
+}
+
+pub type ListSecretsResponseEntry = InspectSecretResponse;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SecretSpec {
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "Driver")]
+    pub driver: Option<SecretDriverSpec>,
+    #[serde(rename = "Labels")]
+    pub labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SecretDriverSpec {
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "Options")]
+    pub options: Option<HashMap<String, String>>,
+}
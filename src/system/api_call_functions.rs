@@ -5,7 +5,7 @@ use crate::system::response_types::{Event, GetInfoResponse};
 use crate::utils;
 use futures::Stream;
 use std::convert::TryInto;
-#[cfg(feature = "enable-tracing")]
+#[cfg(feature = "tracing")]
 use tracing::instrument;
 
 /// System operations.
@@ -19,34 +19,43 @@ impl<'service> System<'service> {
         System { podman_service }
     }
 
+    /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SystemPing>
+    /// Succeeds once the service answers requests.
+    #[cfg_attr(feature = "tracing", instrument(name = "System.ping"))]
+    pub async fn ping(&self) -> Result<()> {
+        let endpoint = "/libpod/_ping";
+        let response = self
+            .podman_service
+            .get_request(endpoint, None, None, None)
+            .await?;
+        utils::check_service_response_for_error(response)
+    }
+
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SystemInfoLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "System.get_info"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "System.get_info"))]
     pub async fn get_info(&self) -> Result<GetInfoResponse> {
-        let endpoint = utils::create_endpoint("/libpod/info");
+        let endpoint = "/libpod/info";
         let response = self
             .podman_service
-            .get_request(&endpoint, None, None, None)
+            .get_request(endpoint, None, None, None)
             .await?;
         utils::deserialize_service_response(response)
     }
 
     /// Filter `image=name_or_id` only seems to apply to image events like pull, push, remove, etc. Not to container events using this image.
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/SystemEventsLibpod>
-    #[cfg_attr(
-        feature = "enable-tracing",
-        instrument(name = "System.get_events_streaming")
-    )]
+    #[cfg_attr(feature = "tracing", instrument(name = "System.get_events_streaming"))]
     pub async fn get_events_streaming(
         &self,
         parameter: EventsParameter,
     ) -> Result<impl Stream<Item = Result<Event>>> {
-        let endpoint = utils::create_endpoint("/libpod/events");
+        let endpoint = "/libpod/events";
         let parameter: EventsParameterStreamingQuery = parameter.try_into()?;
         let query = serde_qs::to_string(&parameter)?;
 
         let (_status_code, result_stream, _header_map) = self
             .podman_service
-            .get_json_stream(&endpoint, Some(query), None, None)
+            .get_json_stream(endpoint, Some(query), None, None)
             .await?;
         Ok(result_stream)
     }
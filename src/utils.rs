@@ -1,25 +1,23 @@
+use crate::containers::parameter_types::TerminalSize;
 use crate::error::{PodtenderError, Result};
 use crate::podman_service::network_internals::PodmanServiceResponseBytes;
 use crate::podtender_errors::PodmanErrorResponse;
 use crate::podtender_errors::RequestError;
-use crate::PODMAN_API_VERSION;
+use base64::Engine;
+use futures::{Stream, StreamExt};
+use hyper::HeaderMap;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
-#[cfg(feature = "enable-tracing")]
+use std::future::Future;
+#[cfg(feature = "tracing")]
 use tracing::{debug, warn};
 
-/// Creates the endpoint part of the request. Change `PODMAN_API_VERSION` to switch to a different
-/// version of the api (assuming the endpoints didn't change).
-pub(crate) fn create_endpoint(endpoint: &str) -> String {
-    format!("{}{}", PODMAN_API_VERSION, endpoint)
-}
-
 /// This method deserializes the response from the podman service into the expected type `T`. Type `T`
 /// is provided by the api call function.
 pub(crate) fn deserialize_service_response<T: DeserializeOwned + std::fmt::Debug>(
     service_response: PodmanServiceResponseBytes,
 ) -> Result<T> {
-    #[cfg(feature = "enable-tracing")]
+    #[cfg(feature = "tracing")]
     {
         let tmp = String::from_utf8(service_response.body.to_vec());
         debug!(raw_response = ?tmp);
@@ -36,14 +34,14 @@ pub(crate) fn deserialize_service_response<T: DeserializeOwned + std::fmt::Debug
     match serde_path_to_error::deserialize(response_deserializer) {
         // deserialization worked
         Ok(result) => {
-            #[cfg(feature = "enable-tracing")]
+            #[cfg(feature = "tracing")]
             debug!(deserialized_response = ?result);
             Ok(result)
         }
         Err(error) => {
             // deserialization didn't work but the http response code indicates success -> issue with deserialization
             if service_response.status_code.is_success() {
-                #[cfg(feature = "enable-tracing")]
+                #[cfg(feature = "tracing")]
                 warn!(
                     ?error,
                     "Status code indicates success but deserialization failed"
@@ -80,7 +78,7 @@ pub(crate) fn handle_service_response_error(
     if let Ok(podman_error) =
         serde_json::from_slice::<PodmanErrorResponse>(service_response.body.as_ref())
     {
-        #[cfg(feature = "enable-tracing")]
+        #[cfg(feature = "tracing")]
         warn!(?podman_error);
 
         podman_error.into()
@@ -99,7 +97,7 @@ pub(crate) fn handle_service_response_error(
             response_code: service_response.status_code.as_u16(),
         };
 
-        #[cfg(feature = "enable-tracing")]
+        #[cfg(feature = "tracing")]
         warn!(?request_error,);
 
         request_error.into()
@@ -118,3 +116,35 @@ pub(crate) fn convert_from_map_to_json_string(
         None
     })
 }
+
+/// Decodes a header containing base64 encoded json, e.g. `X-Docker-Container-Path-Stat`.
+/// Returns `None` if the header isn't present. Podman encodes these headers url safe, the
+/// standard alphabet is accepted too since docker uses it.
+pub(crate) fn decode_base64_json_header<T: DeserializeOwned>(
+    headers: &HeaderMap,
+    name: &str,
+) -> Result<Option<T>> {
+    let value = match headers.get(name) {
+        Some(value) => value.as_bytes(),
+        None => return Ok(None),
+    };
+    let json = base64::engine::general_purpose::URL_SAFE
+        .decode(value)
+        .or_else(|_| base64::engine::general_purpose::STANDARD.decode(value))?;
+    Ok(Some(serde_json::from_slice(&json)?))
+}
+
+/// Calls `resize` for every size received from `sizes`, used by `Containers::forward_resizes` and
+/// `Exec::forward_resizes`. Stops at the first failed resize.
+pub(crate) async fn forward_resizes<S, F, Fut>(sizes: S, mut resize: F) -> Result<()>
+where
+    S: Stream<Item = TerminalSize>,
+    F: FnMut(TerminalSize) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    futures::pin_mut!(sizes);
+    while let Some(size) = sizes.next().await {
+        resize(size).await?;
+    }
+    Ok(())
+}
@@ -7,7 +7,7 @@ use crate::volumes::response_types::{
     PruneVolumesResponseEntry,
 };
 use std::convert::TryInto;
-#[cfg(feature = "enable-tracing")]
+#[cfg(feature = "tracing")]
 use tracing::instrument;
 
 /// Volumes operations.
@@ -22,22 +22,21 @@ impl<'service> Volumes<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/VolumeCreateLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Volumes.create"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Volumes.create"))]
     pub async fn create(&self, parameter: CreateVolumeParameter) -> Result<CreateVolumeResponse> {
-        let endpoint = utils::create_endpoint("/libpod/volumes/create");
+        let endpoint = "/libpod/volumes/create";
         let body = serde_json::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, None, None, Some(body))
+            .post_request(endpoint, None, None, Some(body))
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/VolumeDeleteLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Volumes.remove"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Volumes.remove"))]
     pub async fn remove(&self, parameter: RemoveVolumeParameter) -> Result<()> {
-        let endpoint =
-            utils::create_endpoint(&format!("/libpod/volumes/{}", parameter.volume_name));
+        let endpoint = format!("/libpod/volumes/{}", parameter.volume_name);
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
@@ -47,10 +46,9 @@ impl<'service> Volumes<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/VolumeExistsLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Volumes.exists"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Volumes.exists"))]
     pub async fn exists(&self, param: VolumeExistsParameter) -> Result<()> {
-        let endpoint =
-            utils::create_endpoint(&format!("/libpod/volumes/{}/exists", param.volume_name));
+        let endpoint = format!("/libpod/volumes/{}/exists", param.volume_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -59,10 +57,9 @@ impl<'service> Volumes<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/VolumeCreateLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Volumes.inspect"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Volumes.inspect"))]
     pub async fn inspect(&self, param: InspectVolumeParameter) -> Result<InspectVolumeResponse> {
-        let endpoint =
-            utils::create_endpoint(&format!("/libpod/volumes/{}/json", param.volume_name));
+        let endpoint = format!("/libpod/volumes/{}/json", param.volume_name);
         let service_response = self
             .podman_service
             .get_request(&endpoint, None, None, None)
@@ -71,35 +68,35 @@ impl<'service> Volumes<'service> {
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/VolumeListLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Volumes.list"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Volumes.list"))]
     pub async fn list(
         &self,
         parameter: ListVolumesParameter,
     ) -> Result<Vec<ListVolumesResponseEntry>> {
         let parameter: ListVolumesParameterQuery = parameter.try_into()?;
-        let endpoint = utils::create_endpoint("/libpod/volumes/json");
+        let endpoint = "/libpod/volumes/json";
 
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .get_request(&endpoint, Some(query), None, None)
+            .get_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
 
     /// <https://docs.podman.io/en/latest/_static/api.html?version=v4.0#operation/VolumePruneLibpod>
-    #[cfg_attr(feature = "enable-tracing", instrument(name = "Volumes.prune"))]
+    #[cfg_attr(feature = "tracing", instrument(name = "Volumes.prune"))]
     pub async fn prune(
         &self,
         parameter: PruneVolumesParameter,
     ) -> Result<Vec<PruneVolumesResponseEntry>> {
         let parameter: PruneVolumesParameterQuery = parameter.try_into()?;
-        let endpoint = utils::create_endpoint("/libpod/volumes/prune");
+        let endpoint = "/libpod/volumes/prune";
 
         let query = serde_qs::to_string(&parameter)?;
         let service_response = self
             .podman_service
-            .post_request(&endpoint, Some(query), None, None)
+            .post_request(endpoint, Some(query), None, None)
             .await?;
         utils::deserialize_service_response(service_response)
     }
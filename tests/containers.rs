@@ -1,7 +1,10 @@
 mod utils;
+use futures::sink::SinkExt;
 use futures::stream::StreamExt;
 use podtender::containers::parameter_types::*;
-use podtender::containers::response_types::InspectContainerResponse;
+use podtender::containers::response_types::{
+    AttachOutput, FilesystemChange, FilesystemChangeKind, InspectContainerResponse, LogStream,
+};
 use podtender::error::PodtenderError;
 use podtender::example_values_trait::ExampleValues;
 use serial_test::serial;
@@ -418,6 +421,51 @@ async fn stop_container() {
     }
 }
 
+#[tokio::test]
+async fn attach_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = AttachContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_interactive_container(&container_name);
+
+    let attach = podman_service.containers().attach(parameter).await;
+    if let Err(err) = attach {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+    let (mut stdin, output) = attach.unwrap();
+    futures::pin_mut!(output);
+
+    if let Err(err) = stdin
+        .send(b"attach_container_from_example\n".to_vec())
+        .await
+    {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+
+    let mut stdout = vec![];
+    while let Some(chunk) = output.next().await {
+        match chunk {
+            Ok(AttachOutput::Stdout(mut bytes)) => {
+                stdout.append(&mut bytes);
+                if stdout.ends_with(b"\n") {
+                    break;
+                }
+            }
+            Ok(AttachOutput::Stderr(_)) => {}
+            Err(err) => {
+                utils::delete_container(&container_name);
+                panic!("{:#?}", err);
+            }
+        }
+    }
+    utils::delete_container(&container_name);
+
+    assert_eq!(b"attach_container_from_example\n".to_vec(), stdout);
+}
+
 // Needs root for CRIU to be able to checkpoint a container
 #[tokio::test]
 async fn checkpoint_container_from_example() {
@@ -492,6 +540,164 @@ async fn container_exists_from_example() {
     }
 }
 
+#[tokio::test]
+async fn changes_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = ContainerChangesParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_container(&container_name);
+    utils::exec_in_container(&container_name, &["touch", "/podtender_changes"]);
+
+    let podtender_result = podman_service.containers().changes(parameter).await;
+
+    utils::delete_container(&container_name);
+
+    match podtender_result {
+        Ok(changes) => assert!(changes.contains(&FilesystemChange {
+            path: String::from("/podtender_changes"),
+            kind: FilesystemChangeKind::Added,
+        })),
+        Err(err) => {
+            utils::print_path_if_serde_error(&err);
+            panic!("{:#?}", err);
+        }
+    }
+}
+
+#[tokio::test]
+async fn commit_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = CommitContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+    let image_name = parameter.repo.clone().unwrap();
+
+    utils::create_container(&container_name);
+
+    let podtender_result = podman_service.containers().commit(parameter).await;
+
+    utils::delete_container(&container_name);
+
+    match podtender_result {
+        Ok(response) => {
+            utils::delete_image(&image_name);
+            assert!(!response.id.is_empty());
+        }
+        Err(err) => {
+            utils::print_path_if_serde_error(&err);
+            panic!("{:#?}", err);
+        }
+    }
+}
+
+#[tokio::test]
+async fn copy_from_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = CopyFromContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_container(&container_name);
+
+    let podtender_result = podman_service.containers().copy_from(parameter).await;
+    if let Err(err) = podtender_result {
+        utils::delete_container(&container_name);
+        utils::print_path_if_serde_error(&err);
+        panic!("{:#?}", err);
+    }
+    let (path_stat, archive) = podtender_result.unwrap();
+    let archive: Vec<_> = archive.collect().await;
+
+    utils::delete_container(&container_name);
+
+    assert_eq!(Some(String::from("hostname")), path_stat.unwrap().name);
+    let mut size = 0;
+    for chunk in archive {
+        match chunk {
+            Ok(chunk) => size += chunk.len(),
+            Err(err) => panic!("{:#?}", err),
+        }
+    }
+    assert!(size > 0);
+}
+
+#[tokio::test]
+async fn copy_to_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = CopyToContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_container(&container_name);
+
+    // Copy a file out of the container to get a valid archive to copy back in.
+    let copy_from_parameter = CopyFromContainerParameter {
+        container_name: container_name.clone(),
+        path: String::from("/etc/hostname"),
+    };
+    let archive = match podman_service
+        .containers()
+        .copy_from(copy_from_parameter)
+        .await
+    {
+        Ok((_path_stat, archive)) => archive.collect::<Vec<_>>().await,
+        Err(err) => {
+            utils::delete_container(&container_name);
+            panic!("{:#?}", err);
+        }
+    };
+
+    let podtender_result = podman_service
+        .containers()
+        .copy_to(parameter, futures::stream::iter(archive))
+        .await;
+    if let Err(err) = podtender_result {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+
+    let check_parameter = CopyFromContainerParameter {
+        container_name: container_name.clone(),
+        path: String::from("/tmp/hostname"),
+    };
+    let check = podman_service.containers().copy_from(check_parameter).await;
+
+    utils::delete_container(&container_name);
+
+    match check {
+        Ok((path_stat, _archive)) => {
+            assert_eq!(Some(String::from("hostname")), path_stat.unwrap().name)
+        }
+        Err(err) => panic!("{:#?}", err),
+    }
+}
+
+#[tokio::test]
+async fn generate_systemd_from_example() {
+    let podman_service = utils::setup();
+    let parameter = GenerateSystemdParameter::example();
+    let container_name = parameter.name.clone();
+    utils::create_container(&container_name);
+
+    let podtender_result = podman_service
+        .containers()
+        .generate_systemd(parameter)
+        .await;
+    utils::delete_container(&container_name);
+
+    let units = match podtender_result {
+        Ok(units) => units,
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    };
+    let unit = units
+        .get(&format!("podtender_{}", container_name))
+        .expect("missing container unit");
+    assert!(unit.contains("Wants=network-online.target"));
+    assert!(unit.contains("Environment=PODTENDER=systemd"));
+    assert!(unit.contains("Restart=on-failure"));
+}
+
 #[tokio::test]
 async fn export_container_from_example() {
     let podman_service = utils::setup();
@@ -646,8 +852,8 @@ async fn logs_container_from_example() {
     let mut logs = logs.unwrap();
     let mut counter: usize = 0;
 
-    while let Some(Ok(line)) = logs.next().await {
-        if !line.contains("Container still running.") {
+    while let Some(Ok(frame)) = logs.next().await {
+        if frame.stream != LogStream::Stdout || frame.bytes != b"Container still running.\n" {
             utils::delete_container(&container_name);
             panic!("Logs don't contain expected string.")
         }
@@ -662,6 +868,42 @@ async fn logs_container_from_example() {
     }
 }
 
+#[tokio::test]
+async fn logs_container_with_timestamps_and_tail() {
+    let podman_service = utils::setup();
+    let container_name = String::from("logs_container_with_timestamps_and_tail");
+
+    utils::run_container(&container_name);
+    // Let the container write a few lines.
+    std::thread::sleep(std::time::Duration::from_secs(5));
+
+    let parameter = ContainerLogsParameter {
+        container_name: container_name.clone(),
+        follow: Some(false),
+        stdout: Some(true),
+        tail: Some(1),
+        timestamps: Some(true),
+        ..Default::default()
+    };
+
+    let logs = podman_service.containers().logs(parameter).await;
+    if let Err(err) = logs {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+    let logs: Vec<_> = logs.unwrap().collect().await;
+    utils::delete_container(&container_name);
+
+    assert_eq!(1, logs.len());
+    match &logs[0] {
+        Ok(frame) => {
+            assert!(frame.timestamp.is_some());
+            assert_eq!(b"Container still running.\n".to_vec(), frame.bytes);
+        }
+        Err(err) => panic!("{:#?}", err),
+    }
+}
+
 #[tokio::test]
 async fn mount_container_from_example() {
     let podman_service = utils::setup();
@@ -813,6 +1055,52 @@ async fn rename_container_from_example() {
     }
 }
 
+#[tokio::test]
+async fn resize_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = ResizeContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_tty_container(&container_name);
+
+    let podtender_result = podman_service.containers().resize(parameter).await;
+
+    utils::delete_container(&container_name);
+
+    if let Err(err) = podtender_result {
+        panic!("{:#?}", err);
+    }
+}
+
+#[tokio::test]
+async fn forward_resizes_to_container() {
+    let podman_service = utils::setup();
+    let container_name = String::from("forward_resizes_to_container");
+
+    utils::run_tty_container(&container_name);
+
+    let sizes = futures::stream::iter(vec![
+        TerminalSize {
+            height: 24,
+            width: 80,
+        },
+        TerminalSize {
+            height: 50,
+            width: 120,
+        },
+    ]);
+    let podtender_result = podman_service
+        .containers()
+        .forward_resizes(container_name.clone(), sizes)
+        .await;
+
+    utils::delete_container(&container_name);
+
+    if let Err(err) = podtender_result {
+        panic!("{:#?}", err);
+    }
+}
+
 #[tokio::test]
 async fn restart_container_from_example() {
     let podman_service = utils::setup();
@@ -834,6 +1122,86 @@ async fn restart_container_from_example() {
     }
 }
 
+// Needs root for CRIU to be able to checkpoint and restore a container
+#[tokio::test]
+async fn restore_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = RestoreContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_container(&container_name);
+
+    let checkpoint_parameter = CheckpointContainerParameter {
+        container_name: container_name.clone(),
+        export: Some(false),
+        ..CheckpointContainerParameter::example()
+    };
+    let checkpoint = podman_service
+        .containers()
+        .checkpoint(checkpoint_parameter)
+        .await;
+    if let Err(err) = checkpoint {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+    // Wait for the checkpoint to finish.
+    let _: Vec<_> = checkpoint.unwrap().collect().await;
+
+    let podtender_result = podman_service.containers().restore(parameter).await;
+
+    utils::delete_container(&container_name);
+
+    if let Err(err) = podtender_result {
+        utils::print_path_if_serde_error(&err);
+        panic!("{:#?}", err);
+    }
+}
+
+// Needs root for CRIU to be able to checkpoint and restore a container
+#[tokio::test]
+async fn restore_container_with_import() {
+    let podman_service = utils::setup();
+    let container_name = String::from("restore_container_with_import");
+    let restored_container_name = String::from("restore_container_with_import_restored");
+
+    utils::run_container(&container_name);
+
+    let checkpoint_parameter = CheckpointContainerParameter {
+        container_name: container_name.clone(),
+        ..CheckpointContainerParameter::example()
+    };
+    let checkpoint = podman_service
+        .containers()
+        .checkpoint(checkpoint_parameter)
+        .await;
+    if let Err(err) = checkpoint {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+    let checkpoint: Vec<_> = checkpoint.unwrap().collect().await;
+
+    let parameter = RestoreContainerParameter {
+        container_name: String::new(),
+        name: Some(restored_container_name.clone()),
+        ..Default::default()
+    };
+    let podtender_result = podman_service
+        .containers()
+        .restore_with_import(parameter, futures::stream::iter(checkpoint))
+        .await;
+
+    utils::delete_container(&container_name);
+    utils::delete_container(&restored_container_name);
+
+    match podtender_result {
+        Ok(response) => assert!(response.id.is_some()),
+        Err(err) => {
+            utils::print_path_if_serde_error(&err);
+            panic!("{:#?}", err);
+        }
+    }
+}
+
 #[tokio::test]
 async fn unmount_container_from_example() {
     let podman_service = utils::setup();
@@ -884,6 +1252,89 @@ async fn unpause_container_from_example() {
     }
 }
 
+#[tokio::test]
+async fn update_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = UpdateContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+    let memory_limit = parameter.resources.memory.clone().unwrap().limit;
+
+    utils::run_container(&container_name);
+
+    let podtender_result = podman_service.containers().update(parameter).await;
+    if let Err(err) = podtender_result {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+
+    let inspect_parameter = InspectContainerParameter {
+        container_name: container_name.clone(),
+        size: None,
+    };
+    let inspect = podman_service.containers().inspect(inspect_parameter).await;
+
+    utils::delete_container(&container_name);
+
+    match inspect {
+        Ok(response) => assert_eq!(memory_limit, response.host_config.unwrap().memory),
+        Err(err) => {
+            utils::print_path_if_serde_error(&err);
+            panic!("{:#?}", err);
+        }
+    }
+}
+
+#[tokio::test]
+async fn wait_container_from_example() {
+    let podman_service = utils::setup();
+    let parameter = WaitContainerParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_container(&container_name);
+
+    let container_name_to_stop = container_name.clone();
+    let stop_thread = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        utils::stop_container(&container_name_to_stop);
+    });
+
+    let podtender_result = podman_service.containers().wait(parameter).await;
+
+    stop_thread.join().expect("Error joining stop thread");
+    utils::delete_container(&container_name);
+
+    if let Err(err) = podtender_result {
+        utils::print_path_if_serde_error(&err);
+        panic!("{:#?}", err);
+    }
+}
+
+#[tokio::test]
+async fn wait_container_with_timeout() {
+    let podman_service = utils::setup();
+    let container_name = String::from("wait_container_with_timeout");
+
+    utils::run_container(&container_name);
+
+    let parameter = WaitContainerParameter {
+        container_name: container_name.clone(),
+        condition: Some(vec![WaitCondition::Exited]),
+        ..Default::default()
+    };
+    let podtender_result = tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        podman_service.containers().wait(parameter),
+    )
+    .await;
+
+    utils::delete_container(&container_name);
+
+    assert!(
+        podtender_result.is_err(),
+        "Wait returned although the container is still running"
+    );
+}
+
 #[tokio::test]
 async fn stats_from_example_streaming() {
     let podman_service = utils::setup();
@@ -0,0 +1,252 @@
+mod utils;
+use futures::stream::StreamExt;
+use podtender::containers::parameter_types::TerminalSize;
+use podtender::example_values_trait::ExampleValues;
+use podtender::exec::parameter_types::*;
+use podtender::exec::response_types::ExecOutput;
+
+#[tokio::test]
+async fn create_exec_from_example() {
+    let podman_service = utils::setup();
+    let parameter = CreateExecParameter::example();
+    let container_name = parameter.container_name.clone();
+
+    utils::run_container(&container_name);
+
+    let podtender_result = podman_service.exec().create(parameter).await;
+
+    utils::delete_container(&container_name);
+
+    if let Err(err) = podtender_result {
+        utils::print_path_if_serde_error(&err);
+        panic!("{:#?}", err);
+    }
+}
+
+#[tokio::test]
+async fn start_exec_from_example() {
+    let podman_service = utils::setup();
+    let container_name = String::from("start_exec_from_example");
+
+    utils::run_container(&container_name);
+
+    let create_parameter = CreateExecParameter {
+        container_name: container_name.clone(),
+        ..CreateExecParameter::example()
+    };
+    let exec_id = match podman_service.exec().create(create_parameter).await {
+        Ok(response) => response.id,
+        Err(err) => {
+            utils::delete_container(&container_name);
+            panic!("{:#?}", err);
+        }
+    };
+
+    let parameter = StartExecParameter {
+        exec_id,
+        ..StartExecParameter::example()
+    };
+    let output = podman_service.exec().start(parameter).await;
+    if let Err(err) = output {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+    let output: Vec<_> = output.unwrap().collect().await;
+
+    utils::delete_container(&container_name);
+
+    let mut stdout = vec![];
+    let mut stderr = vec![];
+    let mut exit_code = None;
+    for item in output {
+        match item {
+            Ok(ExecOutput::Stdout(mut bytes)) => stdout.append(&mut bytes),
+            Ok(ExecOutput::Stderr(mut bytes)) => stderr.append(&mut bytes),
+            Ok(ExecOutput::ExitCode(code)) => exit_code = code,
+            Err(err) => panic!("{:#?}", err),
+        }
+    }
+    assert_eq!(b"stdout\n".to_vec(), stdout);
+    assert_eq!(b"stderr\n".to_vec(), stderr);
+    assert_eq!(Some(0), exit_code);
+}
+
+#[tokio::test]
+async fn start_exec_with_exit_code() {
+    let podman_service = utils::setup();
+    let container_name = String::from("start_exec_with_exit_code");
+
+    utils::run_container(&container_name);
+
+    let create_parameter = CreateExecParameter {
+        container_name: container_name.clone(),
+        attach_stdout: Some(true),
+        cmd: Some(vec![
+            String::from("sh"),
+            String::from("-c"),
+            String::from("exit 3"),
+        ]),
+        ..Default::default()
+    };
+    let exec_id = match podman_service.exec().create(create_parameter).await {
+        Ok(response) => response.id,
+        Err(err) => {
+            utils::delete_container(&container_name);
+            panic!("{:#?}", err);
+        }
+    };
+
+    let parameter = StartExecParameter {
+        exec_id,
+        ..Default::default()
+    };
+    let output = podman_service.exec().start(parameter).await;
+    if let Err(err) = output {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+    let last = output.unwrap().collect::<Vec<_>>().await.pop();
+
+    utils::delete_container(&container_name);
+
+    match last {
+        Some(Ok(ExecOutput::ExitCode(code))) => assert_eq!(Some(3), code),
+        other => panic!("Expected exit code as last item, got {:#?}", other),
+    }
+}
+
+#[tokio::test]
+async fn inspect_exec() {
+    let podman_service = utils::setup();
+    let container_name = String::from("inspect_exec");
+
+    utils::run_container(&container_name);
+
+    let create_parameter = CreateExecParameter {
+        container_name: container_name.clone(),
+        ..CreateExecParameter::example()
+    };
+    let exec_id = match podman_service.exec().create(create_parameter).await {
+        Ok(response) => response.id,
+        Err(err) => {
+            utils::delete_container(&container_name);
+            panic!("{:#?}", err);
+        }
+    };
+
+    let parameter = InspectExecParameter {
+        exec_id: exec_id.clone(),
+    };
+    let podtender_result = podman_service.exec().inspect(parameter).await;
+
+    utils::delete_container(&container_name);
+
+    match podtender_result {
+        Ok(response) => {
+            assert_eq!(Some(exec_id), response.id);
+            assert_eq!(Some(false), response.running);
+        }
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn resize_exec() {
+    let podman_service = utils::setup();
+    let container_name = String::from("resize_exec");
+
+    utils::run_container(&container_name);
+
+    let create_parameter = CreateExecParameter {
+        container_name: container_name.clone(),
+        attach_stdout: Some(true),
+        cmd: Some(vec![String::from("sleep"), String::from("10")]),
+        tty: Some(true),
+        ..Default::default()
+    };
+    let exec_id = match podman_service.exec().create(create_parameter).await {
+        Ok(response) => response.id,
+        Err(err) => {
+            utils::delete_container(&container_name);
+            panic!("{:#?}", err);
+        }
+    };
+
+    let start_parameter = StartExecParameter {
+        exec_id: exec_id.clone(),
+        detach: Some(true),
+        tty: Some(true),
+        ..Default::default()
+    };
+    if let Err(err) = podman_service.exec().start(start_parameter).await {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+
+    let parameter = ResizeExecParameter {
+        exec_id,
+        ..ResizeExecParameter::example()
+    };
+    let podtender_result = podman_service.exec().resize(parameter).await;
+
+    utils::delete_container(&container_name);
+
+    if let Err(err) = podtender_result {
+        panic!("{:#?}", err);
+    }
+}
+
+#[tokio::test]
+async fn forward_resizes_to_exec() {
+    let podman_service = utils::setup();
+    let container_name = String::from("forward_resizes_to_exec");
+
+    utils::run_container(&container_name);
+
+    let create_parameter = CreateExecParameter {
+        container_name: container_name.clone(),
+        attach_stdout: Some(true),
+        cmd: Some(vec![String::from("sleep"), String::from("10")]),
+        tty: Some(true),
+        ..Default::default()
+    };
+    let exec_id = match podman_service.exec().create(create_parameter).await {
+        Ok(response) => response.id,
+        Err(err) => {
+            utils::delete_container(&container_name);
+            panic!("{:#?}", err);
+        }
+    };
+
+    let start_parameter = StartExecParameter {
+        exec_id: exec_id.clone(),
+        detach: Some(true),
+        tty: Some(true),
+        ..Default::default()
+    };
+    if let Err(err) = podman_service.exec().start(start_parameter).await {
+        utils::delete_container(&container_name);
+        panic!("{:#?}", err);
+    }
+
+    let sizes = futures::stream::iter(vec![
+        TerminalSize {
+            height: 24,
+            width: 80,
+        },
+        TerminalSize {
+            height: 50,
+            width: 120,
+        },
+    ]);
+    let podtender_result = podman_service.exec().forward_resizes(exec_id, sizes).await;
+
+    utils::delete_container(&container_name);
+
+    if let Err(err) = podtender_result {
+        panic!("{:#?}", err);
+    }
+}
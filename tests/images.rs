@@ -3,7 +3,8 @@ use futures::stream::StreamExt;
 use podtender::error::PodtenderError;
 use podtender::example_values_trait::ExampleValues;
 use podtender::images::parameter_types::*;
-use podtender::images::response_types::InspectImageResponse;
+use podtender::images::response_types::{BuildImageProgress, InspectImageResponse};
+use std::collections::HashMap;
 
 #[tokio::test]
 async fn remove_from_example() {
@@ -55,6 +56,27 @@ async fn exists_from_example() {
     }
 }
 
+#[tokio::test]
+async fn changes_from_example() {
+    let podman_service = utils::setup();
+
+    let parameter = ImageChangesParameter::example();
+    let image_name = parameter.image_name.clone();
+
+    utils::tag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+
+    let podtender_result = podman_service.images().changes(parameter).await;
+    utils::untag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+
+    match podtender_result {
+        Ok(changes) => assert!(!changes.is_empty()),
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
 #[tokio::test]
 async fn does_not_exists_from_example() {
     let podman_service = utils::setup();
@@ -336,3 +358,184 @@ async fn load() {
         }
     }
 }
+
+#[tokio::test]
+async fn build_from_example() {
+    let podman_service = utils::setup();
+    let parameter = BuildImageParameter::example();
+    let image_name = parameter.tags.clone().unwrap().remove(0);
+
+    let context = utils::create_build_context_archive();
+    let context_stream = futures::stream::once(async { Ok::<_, PodtenderError>(context) });
+
+    let progress = podman_service
+        .images()
+        .build(parameter, context_stream)
+        .await;
+    let progress: Vec<_> = match progress {
+        Ok(progress) => progress.collect().await,
+        Err(podtender_error) => panic!("{:#?}", podtender_error),
+    };
+
+    utils::delete_image(&image_name);
+
+    match progress.last() {
+        Some(Ok(BuildImageProgress::ImageId(id))) => assert!(!id.is_empty()),
+        other => panic!("Expected image id as last item, got {:#?}", other),
+    }
+}
+
+#[tokio::test]
+async fn push_from_example() {
+    let podman_service = utils::setup();
+    let parameter = PushImageParameter::example();
+    let image_name = parameter.image_name.clone();
+
+    utils::tag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+
+    let progress = podman_service.images().push(parameter).await;
+    let progress: Vec<_> = match progress {
+        Ok(progress) => progress.collect().await,
+        Err(podtender_error) => {
+            utils::untag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+            panic!("{:#?}", podtender_error);
+        }
+    };
+
+    utils::untag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+    let _ = std::fs::remove_file("/tmp/push_image_parameter.tar");
+
+    let mut manifest_digest = None;
+    for message in progress {
+        match message {
+            Ok(message) => {
+                assert_eq!(None, message.error);
+                if message.manifest_digest.is_some() {
+                    manifest_digest = message.manifest_digest;
+                }
+            }
+            Err(podtender_error) => {
+                utils::print_path_if_serde_error(&podtender_error);
+                panic!("{:#?}", podtender_error);
+            }
+        }
+    }
+    assert!(manifest_digest.is_some());
+}
+
+#[tokio::test]
+async fn history_from_example() {
+    let podman_service = utils::setup();
+    let parameter = ImageHistoryParameter::example();
+    let image_name = parameter.image_name.clone();
+
+    utils::tag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+
+    let podtender_result = podman_service.images().history(parameter).await;
+    utils::untag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+
+    match podtender_result {
+        Ok(history) => assert!(history.iter().any(|entry| entry
+            .created_by
+            .as_ref()
+            .is_some_and(|created_by| created_by.contains("run.sh")))),
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn tag_from_example() {
+    let podman_service = utils::setup();
+    let parameter = TagImageParameter::example();
+    let new_name = format!("{}:latest", parameter.repo);
+
+    let podtender_result = podman_service.images().tag(parameter).await;
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+
+    let exists = podman_service
+        .images()
+        .exists(ImageExistsParameter {
+            image_name: new_name.clone(),
+        })
+        .await;
+    utils::untag_image(utils::TESTCONTAINER_IMAGE_NAME, &new_name);
+
+    if let Err(podtender_error) = exists {
+        panic!("{:#?}", podtender_error);
+    }
+}
+
+#[tokio::test]
+async fn untag_from_example() {
+    let podman_service = utils::setup();
+    let parameter = UntagImageParameter::example();
+    let name = format!("{}:latest", parameter.repo.clone().unwrap());
+
+    utils::tag_image(utils::TESTCONTAINER_IMAGE_NAME, &name);
+
+    let podtender_result = podman_service.images().untag(parameter).await;
+    if let Err(podtender_error) = podtender_result {
+        utils::untag_image(utils::TESTCONTAINER_IMAGE_NAME, &name);
+        panic!("{:#?}", podtender_error);
+    }
+
+    let exists = podman_service
+        .images()
+        .exists(ImageExistsParameter { image_name: name })
+        .await;
+    assert!(exists.is_err(), "Image name still exists after untag");
+}
+
+#[tokio::test]
+async fn tree_from_example() {
+    let podman_service = utils::setup();
+    let parameter = ImageTreeParameter::example();
+    let image_name = parameter.image_name.clone();
+
+    utils::tag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+
+    let podtender_result = podman_service.images().tree(parameter).await;
+    utils::untag_image(utils::TESTCONTAINER_IMAGE_NAME, &image_name);
+
+    match podtender_result {
+        Ok(response) => assert!(response.tree.unwrap().contains(&image_name)),
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn layer_usage_of_test_image() {
+    let podman_service = utils::setup();
+    let mut filter_map: HashMap<String, Vec<String>> = HashMap::new();
+    filter_map.insert(
+        String::from("reference"),
+        vec![String::from(utils::TESTCONTAINER_IMAGE_NAME)],
+    );
+    let parameter = ListImagesParameter {
+        all: Some(false),
+        filters: Some(filter_map),
+    };
+
+    let podtender_result = podman_service.images().layer_usage(parameter).await;
+
+    match podtender_result {
+        Ok(usage) => {
+            let usage = usage.first().expect("Test image not listed");
+            assert!(usage.sizes_complete);
+            assert!(usage.total_size > 0);
+            assert_eq!(usage.total_size, usage.unique_size + usage.shared_size);
+        }
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
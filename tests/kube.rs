@@ -0,0 +1,131 @@
+mod utils;
+use podtender::example_values_trait::ExampleValues;
+use podtender::kube::parameter_types::*;
+
+/// Pod running the test image, the pod is named `pod_name`.
+fn kube_yaml(pod_name: &str) -> String {
+    format!(
+        "apiVersion: v1
+kind: Pod
+metadata:
+  name: {}
+spec:
+  containers:
+  - name: testcontainer
+    image: localhost/{}:latest
+",
+        pod_name,
+        utils::TESTCONTAINER_IMAGE_NAME
+    )
+}
+
+#[tokio::test]
+async fn play_kube_from_example() {
+    let podman_service = utils::setup();
+    let pod_name = "podtender-play-kube";
+    let parameter = PlayKubeParameter::example();
+
+    let podtender_result = podman_service
+        .kube()
+        .play(parameter, kube_yaml(pod_name))
+        .await;
+    utils::delete_pod(pod_name);
+
+    match podtender_result {
+        Ok(report) => {
+            let pods = report.pods.unwrap();
+            assert_eq!(1, pods.len());
+            assert_eq!(Some(1), pods[0].containers.as_ref().map(|c| c.len()));
+        }
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn play_kube_with_config_map() {
+    let podman_service = utils::setup();
+    let pod_name = "podtender-play-kube-config-map";
+    let yaml = format!(
+        "{}    env:
+    - name: PODTENDER
+      valueFrom:
+        configMapKeyRef:
+          name: podtender-config
+          key: value
+",
+        kube_yaml(pod_name)
+    );
+    let config_map = String::from(
+        "apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: podtender-config
+data:
+  value: config
+",
+    );
+    let parameter = PlayKubeParameter {
+        config_maps: Some(vec![config_map]),
+        start: Some(false),
+        ..Default::default()
+    };
+
+    let podtender_result = podman_service.kube().play(parameter, yaml).await;
+    utils::delete_pod(pod_name);
+
+    if let Err(podtender_error) = podtender_result {
+        utils::print_path_if_serde_error(&podtender_error);
+        panic!("{:#?}", podtender_error);
+    }
+}
+
+#[tokio::test]
+async fn kube_down_from_example() {
+    let podman_service = utils::setup();
+    let pod_name = "podtender-kube-down";
+    let play_parameter = PlayKubeParameter {
+        start: Some(false),
+        ..Default::default()
+    };
+    if let Err(podtender_error) = podman_service
+        .kube()
+        .play(play_parameter, kube_yaml(pod_name))
+        .await
+    {
+        utils::delete_pod(pod_name);
+        panic!("{:#?}", podtender_error);
+    }
+
+    let podtender_result = podman_service
+        .kube()
+        .down(KubeDownParameter::example(), kube_yaml(pod_name))
+        .await;
+    utils::delete_pod(pod_name);
+
+    match podtender_result {
+        Ok(report) => assert_eq!(Some(1), report.rm_report.map(|r| r.len())),
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn generate_kube_from_example() {
+    let podman_service = utils::setup();
+    let parameter = GenerateKubeParameter::example();
+    let pod_name = parameter.names[0].clone();
+    utils::create_pod(&pod_name);
+
+    let podtender_result = podman_service.kube().generate(parameter).await;
+    utils::delete_pod(&pod_name);
+
+    match podtender_result {
+        Ok(yaml) => assert!(yaml.contains("kind: Pod")),
+        Err(podtender_error) => panic!("{:#?}", podtender_error),
+    }
+}
@@ -0,0 +1,213 @@
+mod utils;
+use futures::StreamExt;
+use podtender::example_values_trait::ExampleValues;
+use podtender::manifests::parameter_types::*;
+use podtender::podman_service::PodmanService;
+
+/// Digest of the first instance of the manifest list `manifest_name`.
+async fn first_instance_digest(podman_service: &PodmanService, manifest_name: &str) -> String {
+    let manifest_list = podman_service
+        .manifests()
+        .inspect(InspectManifestParameter {
+            manifest_name: manifest_name.to_owned(),
+            tls_verify: None,
+        })
+        .await;
+    match manifest_list {
+        Ok(manifest_list) => manifest_list
+            .manifests
+            .and_then(|manifests| manifests.into_iter().next())
+            .and_then(|descriptor| descriptor.digest)
+            .expect("manifest list without instances"),
+        Err(podtender_error) => {
+            utils::delete_manifest(manifest_name);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn create_manifest_from_example() {
+    let podman_service = utils::setup();
+    let parameter = CreateManifestParameter::example();
+    let manifest_name = parameter.manifest_name.clone();
+
+    let podtender_result = podman_service.manifests().create(parameter).await;
+    utils::delete_manifest(&manifest_name);
+
+    match podtender_result {
+        Ok(response) => assert!(response.id.is_some()),
+        Err(podtender_error) => panic!("{:#?}", podtender_error),
+    }
+}
+
+#[tokio::test]
+async fn inspect_manifest_from_example() {
+    let podman_service = utils::setup();
+    let parameter = InspectManifestParameter::example();
+    let manifest_name = parameter.manifest_name.clone();
+    utils::create_manifest(&manifest_name);
+
+    let podtender_result = podman_service.manifests().inspect(parameter).await;
+    utils::delete_manifest(&manifest_name);
+
+    match podtender_result {
+        Ok(manifest_list) => {
+            assert_eq!(Some(1), manifest_list.manifests.map(|m| m.len()));
+        }
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn manifest_exists_from_example() {
+    let podman_service = utils::setup();
+    let parameter = ManifestExistsParameter::example();
+    let manifest_name = parameter.manifest_name.clone();
+    utils::create_manifest(&manifest_name);
+
+    let podtender_result = podman_service.manifests().exists(parameter).await;
+    utils::delete_manifest(&manifest_name);
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+}
+
+#[tokio::test]
+async fn delete_manifest_from_example() {
+    let podman_service = utils::setup();
+    let parameter = DeleteManifestParameter::example();
+    utils::create_manifest(&parameter.manifest_name);
+
+    let podtender_result = podman_service.manifests().delete(parameter).await;
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+}
+
+#[tokio::test]
+async fn add_to_manifest_from_example() {
+    let podman_service = utils::setup();
+    let parameter = AddToManifestParameter::example();
+    let manifest_name = parameter.manifest_name.clone();
+    podman_service
+        .manifests()
+        .create(CreateManifestParameter {
+            manifest_name: manifest_name.clone(),
+            ..Default::default()
+        })
+        .await
+        .expect("failed to create manifest list");
+
+    let podtender_result = podman_service.manifests().add(parameter).await;
+    let manifest_list = podman_service
+        .manifests()
+        .inspect(InspectManifestParameter {
+            manifest_name: manifest_name.clone(),
+            tls_verify: None,
+        })
+        .await;
+    utils::delete_manifest(&manifest_name);
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+    let descriptor = manifest_list.unwrap().manifests.unwrap().remove(0);
+    let platform = descriptor.platform.unwrap();
+    assert_eq!(Some(String::from("arm64")), platform.architecture);
+    assert_eq!(Some(String::from("v8")), platform.variant);
+}
+
+#[tokio::test]
+async fn remove_from_manifest_from_example() {
+    let podman_service = utils::setup();
+    let mut parameter = RemoveFromManifestParameter::example();
+    let manifest_name = parameter.manifest_name.clone();
+    utils::create_manifest(&manifest_name);
+    parameter.digest = first_instance_digest(podman_service, &manifest_name).await;
+
+    let podtender_result = podman_service.manifests().remove(parameter).await;
+    let manifest_list = podman_service
+        .manifests()
+        .inspect(InspectManifestParameter {
+            manifest_name: manifest_name.clone(),
+            tls_verify: None,
+        })
+        .await;
+    utils::delete_manifest(&manifest_name);
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+    assert_eq!(
+        0,
+        manifest_list.unwrap().manifests.unwrap_or_default().len()
+    );
+}
+
+#[tokio::test]
+async fn annotate_manifest_from_example() {
+    let podman_service = utils::setup();
+    let mut parameter = AnnotateManifestParameter::example();
+    let manifest_name = parameter.manifest_name.clone();
+    utils::create_manifest(&manifest_name);
+    parameter.digest = first_instance_digest(podman_service, &manifest_name).await;
+    let annotations = parameter.annotations.clone();
+
+    let podtender_result = podman_service.manifests().annotate(parameter).await;
+    let manifest_list = podman_service
+        .manifests()
+        .inspect(InspectManifestParameter {
+            manifest_name: manifest_name.clone(),
+            tls_verify: None,
+        })
+        .await;
+    utils::delete_manifest(&manifest_name);
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+    let descriptor = manifest_list.unwrap().manifests.unwrap().remove(0);
+    assert_eq!(annotations, descriptor.annotations);
+}
+
+#[tokio::test]
+async fn push_manifest_from_example() {
+    let podman_service = utils::setup();
+    let parameter = PushManifestParameter::example();
+    let manifest_name = parameter.manifest_name.clone();
+    utils::create_manifest(&manifest_name);
+
+    let progress = podman_service.manifests().push(parameter).await;
+    let progress: Vec<_> = match progress {
+        Ok(progress) => progress.collect().await,
+        Err(podtender_error) => {
+            utils::delete_manifest(&manifest_name);
+            panic!("{:#?}", podtender_error);
+        }
+    };
+    utils::delete_manifest(&manifest_name);
+    let _ = std::fs::remove_dir_all("/tmp/push_manifest_parameter");
+
+    let mut manifest_digest = None;
+    for message in progress {
+        match message {
+            Ok(message) => {
+                assert_eq!(None, message.error);
+                if message.id.is_some() {
+                    manifest_digest = message.id;
+                }
+            }
+            Err(podtender_error) => {
+                utils::print_path_if_serde_error(&podtender_error);
+                panic!("{:#?}", podtender_error);
+            }
+        }
+    }
+    assert!(manifest_digest.is_some());
+}
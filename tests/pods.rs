@@ -1,6 +1,7 @@
 mod utils;
 
 use futures::stream::StreamExt;
+use podtender::containers::parameter_types::GenerateSystemdParameter;
 use podtender::example_values_trait::ExampleValues;
 use podtender::pods::parameter_types::*;
 
@@ -440,3 +441,31 @@ async fn multiple_pods_stats_streaming() {
     assert!(found_container);
     assert!(found_pod);
 }
+
+#[tokio::test]
+async fn generate_systemd_for_pod() {
+    let podman_service = utils::setup();
+    let pod_name = String::from("generate_systemd_for_pod");
+    let container_name = String::from("generate_systemd_for_pod_container");
+    utils::create_pod(&pod_name);
+    utils::create_container_with_pod(&container_name, &pod_name);
+
+    let parameter = GenerateSystemdParameter {
+        name: pod_name.clone(),
+        use_name: Some(true),
+        ..Default::default()
+    };
+    let podtender_result = podman_service.pods().generate_systemd(parameter).await;
+    utils::delete_pod(&pod_name);
+
+    match podtender_result {
+        Ok(units) => {
+            assert!(units.contains_key(&format!("pod-{}", pod_name)));
+            assert!(units.contains_key(&format!("container-{}", container_name)));
+        }
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
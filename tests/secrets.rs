@@ -0,0 +1,104 @@
+mod utils;
+use podtender::example_values_trait::ExampleValues;
+use podtender::secrets::parameter_types::*;
+
+#[tokio::test]
+async fn create_secret_from_example() {
+    let podman_service = utils::setup();
+    let parameter = CreateSecretParameter::example();
+    let secret_name = parameter.name.clone();
+    let data = futures::stream::iter(vec![
+        Ok::<_, std::io::Error>(&b"super"[..]),
+        Ok(&b"secret"[..]),
+    ]);
+
+    let podtender_result = podman_service.secrets().create(parameter, data).await;
+    let secret = podman_service
+        .secrets()
+        .inspect(InspectSecretParameter {
+            secret_name: secret_name.clone(),
+            show_secret: Some(true),
+        })
+        .await;
+    utils::delete_secret(&secret_name);
+
+    match podtender_result {
+        Ok(response) => assert!(response.id.is_some()),
+        Err(podtender_error) => panic!("{:#?}", podtender_error),
+    }
+    match secret {
+        Ok(secret) => assert_eq!(Some(String::from("supersecret")), secret.secret_data),
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn inspect_secret_from_example() {
+    let podman_service = utils::setup();
+    let parameter = InspectSecretParameter::example();
+    let secret_name = parameter.secret_name.clone();
+    utils::create_secret(&secret_name, "inspect");
+
+    let podtender_result = podman_service.secrets().inspect(parameter).await;
+    utils::delete_secret(&secret_name);
+
+    match podtender_result {
+        Ok(secret) => {
+            assert_eq!(Some(secret_name), secret.spec.and_then(|spec| spec.name));
+        }
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn list_secrets_from_example() {
+    let podman_service = utils::setup();
+    let parameter = ListSecretsParameter::example();
+    let secret_name = String::from("list_secrets_parameter");
+    utils::create_secret(&secret_name, "list");
+
+    let podtender_result = podman_service.secrets().list(parameter).await;
+    utils::delete_secret(&secret_name);
+
+    match podtender_result {
+        Ok(secrets) => assert_eq!(1, secrets.len()),
+        Err(podtender_error) => {
+            utils::print_path_if_serde_error(&podtender_error);
+            panic!("{:#?}", podtender_error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn remove_secret_from_example() {
+    let podman_service = utils::setup();
+    let parameter = RemoveSecretParameter::example();
+    utils::create_secret(&parameter.secret_name, "remove");
+
+    let podtender_result = podman_service.secrets().remove(parameter).await;
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+}
+
+#[tokio::test]
+async fn secret_exists_from_example() {
+    let podman_service = utils::setup();
+    let parameter = SecretExistsParameter::example();
+    let secret_name = parameter.secret_name.clone();
+    utils::create_secret(&secret_name, "exists");
+
+    let podtender_result = podman_service.secrets().exists(parameter).await;
+    utils::delete_secret(&secret_name);
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+}
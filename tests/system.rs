@@ -1,10 +1,34 @@
 mod utils;
 use futures::stream::StreamExt;
 use podtender::example_values_trait::ExampleValues;
+use podtender::podman_service::ApiVersion;
 use podtender::system::parameter_types::EventsParameter;
 use podtender::system::response_types::GetInfoResponse;
 use std::collections::HashMap;
 
+#[tokio::test]
+async fn ping() {
+    let podman_service = utils::setup();
+
+    let podtender_result = podman_service.system().ping().await;
+
+    if let Err(podtender_error) = podtender_result {
+        panic!("{:#?}", podtender_error);
+    }
+}
+
+#[tokio::test]
+async fn api_version() {
+    let podman_service = utils::setup();
+
+    let podtender_result = podman_service.api_version().await;
+
+    match podtender_result {
+        Ok(api_version) => assert!(api_version >= ApiVersion::new(4, 0, 0)),
+        Err(podtender_error) => panic!("{:#?}", podtender_error),
+    }
+}
+
 #[tokio::test]
 async fn get_info() {
     let podman_service = utils::setup();
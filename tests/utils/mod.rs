@@ -1,7 +1,7 @@
 use lazy_static::lazy_static;
 use podtender::error::PodtenderError;
 use podtender::error::PodtenderError::SerdeJsonErrorWithPath;
-use podtender::podman_service::PodmanService;
+use podtender::podman_service::{LocalPodmanService, PodmanService};
 use std::path::PathBuf;
 use std::process::{Command, Output};
 use std::thread;
@@ -12,9 +12,8 @@ use tracing_subscriber::util::SubscriberInitExt;
 // #[allow(dead_code)] is need on most methods to calm clippy down.
 
 pub static TESTCONTAINER_IMAGE_NAME: &str = "testcontainer";
-static API_SOCKET_NAME: &str = "podtender_test_socket";
 // In seconds.
-static API_TTL: u32 = 300;
+static API_TTL: u64 = 300;
 
 lazy_static! {
     pub static ref PODMAN_PATH: PathBuf = PathBuf::from("podman");
@@ -25,8 +24,7 @@ lazy_static! {
 }
 
 lazy_static! {
-    pub static ref PODMAN_SERVICE: PodmanService =
-        PodmanService::new(setup_podman_api(API_SOCKET_NAME, API_TTL).as_str());
+    pub static ref PODMAN_SERVICE: LocalPodmanService = setup_podman_api(API_TTL);
 }
 
 lazy_static! {
@@ -50,33 +48,18 @@ pub fn setup() -> &'static PodmanService {
     &PODMAN_SERVICE
 }
 
-/// Start the podman api for `ttl` seconds on `unix:///home/{user}/socket_name.sock`.
-/// `user` is the current system username, `socket_name` is supplied via function parameter.
+/// Start the podman api for `ttl` seconds on a temporary socket and wait until it's ready.
+/// Runs on its own thread since it's set up lazily from within the runtime of a test.
 #[allow(dead_code)]
-pub fn setup_podman_api(socket_name: &str, ttl: u32) -> String {
-    let user = Command::new("whoami")
-        .output()
-        .expect("failed to execute podman command");
-
-    let user = String::from(
-        String::from_utf8(Vec::from(user.stdout.as_slice()))
+pub fn setup_podman_api(ttl: u64) -> LocalPodmanService {
+    thread::spawn(move || {
+        tokio::runtime::Runtime::new()
             .unwrap()
-            .trim(),
-    );
-
-    let socket = format!("unix:///home/{}/{}.sock", user, socket_name);
-    let ttl = format!("--time={}", ttl);
-
-    Command::new(PODMAN_PATH.as_path())
-        .arg("system")
-        .arg("service")
-        .arg(&socket)
-        .arg(ttl)
-        .spawn()
-        .expect("failed to execute podman command");
-    // otherwise the socket isn't always ready, really annoying to debug
-    thread::sleep(time::Duration::from_secs(5));
-    format!("/home/{}/{}.sock", user, socket_name)
+            .block_on(PodmanService::spawn_local(time::Duration::from_secs(ttl)))
+    })
+    .join()
+    .unwrap()
+    .expect("failed to start podman service")
 }
 
 /// Return podman system information.
@@ -101,6 +84,44 @@ pub fn run_container(container_name: &str) {
         .expect("failed to execute podman command");
 }
 
+/// Start a container named `container_name` with an open stdin running `cat` for testing purposes.
+#[allow(dead_code)]
+pub fn run_interactive_container(container_name: &str) {
+    Command::new(PODMAN_PATH.as_path())
+        .arg("run")
+        .arg("-d")
+        .arg("-i")
+        .arg(format!("--name={}", container_name))
+        .arg(format!("{}:latest", TESTCONTAINER_IMAGE_NAME))
+        .arg("cat")
+        .status()
+        .expect("failed to execute podman command");
+}
+
+/// Start a container named `container_name` with a tty for testing purposes.
+#[allow(dead_code)]
+pub fn run_tty_container(container_name: &str) {
+    Command::new(PODMAN_PATH.as_path())
+        .arg("run")
+        .arg("-d")
+        .arg("-t")
+        .arg(format!("--name={}", container_name))
+        .arg(format!("{}:latest", TESTCONTAINER_IMAGE_NAME))
+        .status()
+        .expect("failed to execute podman command");
+}
+
+/// Run `command` inside the running container named `container_name` for testing purposes.
+#[allow(dead_code)]
+pub fn exec_in_container(container_name: &str, command: &[&str]) {
+    Command::new(PODMAN_PATH.as_path())
+        .arg("exec")
+        .arg(container_name)
+        .args(command)
+        .status()
+        .expect("failed to execute podman command");
+}
+
 /// Start a container named `container_name` for testing purposes.
 #[allow(dead_code)]
 pub fn start_container(container_name: &str) {
@@ -270,6 +291,22 @@ pub fn build_test_image() -> bool {
         .success()
 }
 
+/// Create a tar archive of the test_container directory to use as build context.
+#[allow(dead_code)]
+pub fn create_build_context_archive() -> Vec<u8> {
+    use std::env;
+    let cargo_manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let output = Command::new("tar")
+        .arg("-c")
+        .arg("-C")
+        .arg(format!("{}/test_container", cargo_manifest_dir))
+        .arg(".")
+        .output()
+        .expect("failed to execute tar command");
+    assert!(output.status.success());
+    output.stdout
+}
+
 /// Use podman cli to build the container from  test_container/Dockerfile2
 /// Already built images can't be assigned a label as far as I can tell to we need to build a new and different image to avoid just tagging the usual testimage.
 #[allow(dead_code)]
@@ -572,3 +609,59 @@ pub fn print_path_if_serde_error(podtender_error: &PodtenderError) {
         eprintln!("{}", test.path())
     }
 }
+
+/// Create a manifest list containing the test image for testing purposes.
+#[allow(dead_code)]
+pub fn create_manifest(manifest_name: &str) {
+    Command::new(PODMAN_PATH.as_path())
+        .arg("manifest")
+        .arg("create")
+        .arg(manifest_name)
+        .arg(TESTCONTAINER_IMAGE_NAME)
+        .status()
+        .expect("failed to execute podman command");
+}
+
+/// Delete a manifest list for testing purposes.
+#[allow(dead_code)]
+pub fn delete_manifest(manifest_name: &str) {
+    Command::new(PODMAN_PATH.as_path())
+        .arg("manifest")
+        .arg("rm")
+        .arg(manifest_name)
+        .status()
+        .expect("failed to execute podman command");
+}
+
+/// Create a secret containing `data` for testing purposes.
+#[allow(dead_code)]
+pub fn create_secret(secret_name: &str, data: &str) {
+    use std::io::Write;
+    use std::process::Stdio;
+    let mut child = Command::new(PODMAN_PATH.as_path())
+        .arg("secret")
+        .arg("create")
+        .arg(secret_name)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .expect("failed to execute podman command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(data.as_bytes())
+        .unwrap();
+    child.wait().expect("failed to execute podman command");
+}
+
+/// Delete a secret for testing purposes.
+#[allow(dead_code)]
+pub fn delete_secret(secret_name: &str) {
+    Command::new(PODMAN_PATH.as_path())
+        .arg("secret")
+        .arg("rm")
+        .arg(secret_name)
+        .status()
+        .expect("failed to execute podman command");
+}